@@ -1,76 +1,87 @@
 //! Aggregate a set of items with the same stats into a single stand-in item.
+//!
+//! The aggregations are listed in `rules.toml` (see [rules](crate::rules)).
 
-use std::{
-    collections::{HashMap, HashSet},
-    hash::BuildHasher,
-    sync::Mutex,
-};
-
-use lazy_static::lazy_static;
+use std::collections::HashMap;
 
 use crate::osrsbox::ItemProperties;
+use crate::rules::{Aggregation, Origin, Rules};
 
-pub mod broodoo;
-pub mod capes;
-pub mod gods;
-pub mod hunter_gear;
-pub mod other_gods;
-pub mod skill_capes;
-
-/// Add wiki names of items to filter.
-pub fn add_filter_names<S: BuildHasher>(set: &mut HashSet<String, S>) {
-    broodoo::add_filter_names(set);
-    gods::add_filter_names(set);
-    hunter_gear::add_filter_names(set);
-    other_gods::add_filter_names(set);
-    skill_capes::add_filter_names(set);
-    capes::add_filter_names(set);
+/// State of the aggregation during one run of the pipeline.
+#[derive(Debug, Clone, Default)]
+pub struct Aggregator {
+    /// Wiki names of items to be filtered in favor of an aggregated item, mapped to the wiki name
+    /// of the item they are folded into and the rule they originate from.
+    filter_names: HashMap<String, (String, Origin)>,
+    /// Ids of filtered items by the wiki name of the item they are folded into.
+    folded_ids: HashMap<String, Vec<u32>>,
+    /// Aggregations by the wiki name of the item to be aggregated.
+    wiki_names: HashMap<String, Aggregation>,
 }
 
-/// Performs aggregation.
-pub trait Aggregator {
-    /// Aggregate one item.
-    fn aggregate(&self, item: &mut ItemProperties);
-}
-type AggregationMap = HashMap<String, Box<dyn Aggregator + Send>>;
+impl Aggregator {
+    /// Create an aggregator for the aggregations of `rules`.
+    #[must_use]
+    pub fn new(rules: &Rules) -> Self {
+        Self {
+            filter_names: rules.folds.clone(),
+            folded_ids: HashMap::new(),
+            wiki_names: rules.aggregations.clone(),
+        }
+    }
 
-lazy_static! {
-    /// Set of wiki names of items to be aggregated.
-    static ref WIKI_NAMES: Mutex<AggregationMap> = Mutex::new({
-        let mut map = HashMap::new();
+    /// Returns the wiki name of the aggregation target and the originating rule if the item is
+    /// filtered in favor of an aggregated item. Its id is remembered to be added to the aggregate
+    /// later on.
+    ///
+    /// Has to be called for all items before [`apply`](Self::apply).
+    #[must_use]
+    pub fn fold(&mut self, item: &ItemProperties) -> Option<(String, Origin)> {
+        let (target, origin) = self.filter_names.remove(item.wiki_name.as_ref()?)?;
 
-        broodoo::add_aggregators(&mut map);
-        gods::add_aggregators(&mut map);
-        hunter_gear::add_aggregators(&mut map);
-        other_gods::add_aggregators(&mut map);
-        skill_capes::add_aggregators(&mut map);
-        capes::add_aggregators(&mut map);
+        self.folded_ids
+            .entry(target.clone())
+            .or_default()
+            .push(item.id);
+        Some((target, origin))
+    }
 
-        map
-    });
-}
-/// Process the items which are kept.
-///
-/// # Panics
-///
-/// When unable to acquire the global locks.
-pub fn apply_aggregation(item: &mut ItemProperties) {
-    if let Some(agg) = WIKI_NAMES
-        .lock()
-        .unwrap()
-        .remove(item.wiki_name.as_ref().unwrap())
-    {
-        agg.aggregate(item);
+    /// Process the items which are kept.
+    ///
+    /// Returns the ids of all items folded into this one, none if the item has no wiki name.
+    pub fn apply(&mut self, item: &mut ItemProperties) -> Vec<u32> {
+        let Some(wiki_name) = item.wiki_name.as_ref() else {
+            return Vec::new();
+        };
+        let mut folded = self.folded_ids.remove(wiki_name).unwrap_or_default();
+        folded.sort_unstable();
+
+        if let Some(agg) = self.wiki_names.remove(wiki_name) {
+            item.name = agg.name;
+            item.wiki_url = Some(agg.wiki_url);
+        }
+
+        folded
     }
-}
 
-/// Print leftover names in the item list (probably typos).
-///
-/// # Panics
-///
-/// When unable to acquire the global locks.
-pub fn check() {
-    for (name, _) in WIKI_NAMES.lock().unwrap().iter() {
-        println!("Error: Missed aggregation: {}", name);
+    /// Return errors for leftover names in the item list (probably typos), with the rule they
+    /// originate from.
+    #[must_use]
+    pub fn check(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        for (name, (_, origin)) in &self.filter_names {
+            errors.push(format!("Missed aggregation filter: {} ({})", name, origin));
+        }
+
+        for (name, agg) in &self.wiki_names {
+            errors.push(format!("Missed aggregation: {} ({})", name, agg.origin));
+        }
+
+        for name in self.folded_ids.keys() {
+            errors.push(format!("Missed aggregation target: {}", name));
+        }
+
+        errors
     }
 }
@@ -0,0 +1,284 @@
+//! Command line interface.
+//!
+//! Running without a subcommand is the same as running `build`.
+
+use std::path::PathBuf;
+
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
+use data::Features;
+use data_processor::{rules::RULES_FILE, source::OVERRIDES_FILE};
+
+/// OSRSBox item data.
+pub const DATA_URL: &str = "https://www.osrsbox.com/osrsbox-db/items-complete.json";
+/// OSRSBox monster data.
+pub const MONSTER_DATA_URL: &str = "https://www.osrsbox.com/osrsbox-db/monsters-complete.json";
+/// Latest Grand Exchange prices.
+pub const PRICE_DATA_URL: &str = "https://prices.runescape.wiki/api/v1/osrs/latest";
+
+/// Item data file in the output directory.
+pub const ITEM_FILE: &str = "items.bin.xz";
+/// Monster data file in the output directory.
+pub const MONSTER_FILE: &str = "monsters.bin.xz";
+/// Build manifest in the output directory, see [`manifest`](data_processor::manifest).
+pub const MANIFEST_FILE: &str = data::MANIFEST_FILE;
+/// Report of item fates in the output directory, see [`explain`](data_processor::explain).
+pub const EXPLAIN_FILE: &str = "items-explain.json";
+
+/// Builds the item and monster data of the equipment tools.
+#[derive(Debug, Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    /// The action to perform, `build` by default.
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// Options of `build`, when no subcommand is given.
+    #[command(flatten)]
+    pub build: BuildArgs,
+
+    /// Print more details, repeat for even more.
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    pub verbose: u8,
+
+    /// Only print errors.
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+}
+
+impl Cli {
+    /// Output level: `0` when quiet, `1` by default, higher if verbose.
+    #[must_use]
+    pub fn verbosity(&self) -> u8 {
+        if self.quiet {
+            0
+        } else {
+            self.verbose.saturating_add(1)
+        }
+    }
+}
+
+/// Subcommands.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Generate the item and monster data if the inputs changed.
+    Build(BuildArgs),
+    /// Download the OSRSBox data for later builds.
+    Cache {
+        /// Input and output locations.
+        #[command(flatten)]
+        paths: Paths,
+        /// Also download the latest Grand Exchange prices.
+        #[arg(long)]
+        prices: bool,
+    },
+    /// Print what happened to the items with the given name, without writing output.
+    Explain {
+        /// Input and output locations.
+        #[command(flatten)]
+        paths: Paths,
+        /// Item name, wiki name or output name (case insensitive).
+        name: String,
+    },
+    /// Print groups of kept items with identical stats which are not aggregated, with a suggested
+    /// rule for each (see `variants`).
+    Variants {
+        /// Input and output locations.
+        #[command(flatten)]
+        paths: Paths,
+    },
+    /// Print the items added, removed and changed between two item data files.
+    Diff {
+        /// The previous item data.
+        old: PathBuf,
+        /// The new item data, `items.bin.xz` in the output directory by default.
+        new: Option<PathBuf>,
+        /// Input and output locations.
+        #[command(flatten)]
+        paths: Paths,
+    },
+    /// Pretty-print an item data file.
+    Inspect {
+        /// The item data, `items.bin.xz` in the output directory by default.
+        file: Option<PathBuf>,
+        /// Only print items with this name (case insensitive).
+        #[arg(long)]
+        name: Option<String>,
+        /// Input and output locations.
+        #[command(flatten)]
+        paths: Paths,
+    },
+    /// Run the pipeline and all checks without writing output.
+    Validate {
+        /// Input and output locations.
+        #[command(flatten)]
+        paths: Paths,
+    },
+}
+
+/// Options of `build`.
+#[derive(Debug, Clone, Args)]
+pub struct BuildArgs {
+    /// Input and output locations.
+    #[command(flatten)]
+    pub paths: Paths,
+    /// Rebuild even if no input changed.
+    #[arg(short, long)]
+    pub force: bool,
+    /// Download the latest Grand Exchange prices before building.
+    #[arg(long)]
+    pub prices: bool,
+    /// Write nothing if there are any errors, and summarize them at the end.
+    #[arg(long)]
+    pub strict: bool,
+}
+
+/// Input and output locations shared by the subcommands.
+#[derive(Debug, Clone, Args)]
+pub struct Paths {
+    /// Cached OSRSBox item data, downloaded from `--data-url` if missing.
+    #[arg(short, long, default_value = "data/items-complete.json")]
+    pub input: PathBuf,
+    /// OSRS Wiki item export, merged over the OSRSBox item data (see `source::wiki`).
+    #[arg(long)]
+    pub wiki_input: Option<PathBuf>,
+    /// Cached OSRSBox monster data, downloaded from `--monster-data-url` if missing.
+    #[arg(long, default_value = "data/monsters-complete.json")]
+    pub monster_input: PathBuf,
+    /// Cached Grand Exchange prices, prices are skipped if missing.
+    #[arg(long, default_value = "data/prices-latest.json")]
+    pub price_input: PathBuf,
+    /// Filter, folding and armour set rules of the items.
+    #[arg(long, default_value = RULES_FILE)]
+    pub rules: PathBuf,
+    /// Manual corrections merged over the other item sources.
+    #[arg(long, default_value = OVERRIDES_FILE)]
+    pub overrides: PathBuf,
+    /// Directory of the generated files.
+    #[arg(short, long, default_value = "dist")]
+    pub output_dir: PathBuf,
+    /// Source of the OSRSBox item data.
+    #[arg(long, default_value = DATA_URL)]
+    pub data_url: String,
+    /// Source of the OSRSBox monster data.
+    #[arg(long, default_value = MONSTER_DATA_URL)]
+    pub monster_data_url: String,
+    /// Source of the Grand Exchange prices.
+    #[arg(long, default_value = PRICE_DATA_URL)]
+    pub price_data_url: String,
+    /// Feature profile of the data, has to match the features the processor was compiled with.
+    #[arg(long, value_enum, default_value_t = Profile::current())]
+    pub profile: Profile,
+}
+
+impl Paths {
+    /// Path of the item data file.
+    #[must_use]
+    pub fn items(&self) -> PathBuf {
+        self.output_dir.join(ITEM_FILE)
+    }
+
+    /// Path of the monster data file.
+    #[must_use]
+    pub fn monsters(&self) -> PathBuf {
+        self.output_dir.join(MONSTER_FILE)
+    }
+
+    /// Path of the build manifest.
+    #[must_use]
+    pub fn manifest(&self) -> PathBuf {
+        self.output_dir.join(MANIFEST_FILE)
+    }
+
+    /// Path of the item fate report.
+    #[must_use]
+    pub fn report(&self) -> PathBuf {
+        self.output_dir.join(EXPLAIN_FILE)
+    }
+}
+
+/// Feature profile of the generated data, see [`Features`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Profile {
+    /// No optional features.
+    Default,
+    /// Trailblazer league regions.
+    Trailblazer,
+}
+
+impl Profile {
+    /// The profile matching the features the processor was compiled with.
+    #[must_use]
+    pub fn current() -> Self {
+        if Features::current().trailblazer {
+            Self::Trailblazer
+        } else {
+            Self::Default
+        }
+    }
+
+    /// Check that the processor can generate data for this profile.
+    ///
+    /// # Errors
+    ///
+    /// If the processor was compiled with different features.
+    pub fn check(self) -> Result<(), String> {
+        match (self, Self::current()) {
+            (Self::Trailblazer, Self::Default) => Err(
+                "the trailblazer profile requires compiling with `--features trailblazer`"
+                    .to_string(),
+            ),
+            (Self::Default, Self::Trailblazer) => Err(
+                "the default profile requires compiling without `--features trailblazer`"
+                    .to_string(),
+            ),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn verify() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parse() {
+        let cli = Cli::parse_from(["data-processor"]);
+        assert!(cli.command.is_none());
+        assert_eq!(cli.build.paths.items(), PathBuf::from("dist/items.bin.xz"));
+        assert_eq!(cli.build.paths.rules, PathBuf::from(RULES_FILE));
+        assert_eq!(cli.verbosity(), 1);
+
+        let cli = Cli::parse_from([
+            "data-processor",
+            "-o",
+            "out",
+            "--force",
+            "-q",
+            "--overrides",
+            "local.toml",
+        ]);
+        assert_eq!(
+            cli.build.paths.monsters(),
+            PathBuf::from("out/monsters.bin.xz")
+        );
+        assert!(cli.build.force);
+        assert_eq!(cli.build.paths.overrides, PathBuf::from("local.toml"));
+        assert_eq!(cli.verbosity(), 0);
+
+        let cli = Cli::parse_from(["data-processor", "explain", "Rune platebody", "-vv"]);
+        assert!(
+            matches!(&cli.command, Some(Command::Explain { name, .. }) if name == "Rune platebody")
+        );
+        assert_eq!(cli.verbosity(), 3);
+
+        assert!(Cli::try_parse_from(["data-processor", "-q", "-v"]).is_err());
+        assert!(Cli::try_parse_from(["data-processor", "--force", "validate"]).is_err());
+    }
+}
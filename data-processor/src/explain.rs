@@ -0,0 +1,376 @@
+//! Trace the fate of every source item through the pipeline.
+//!
+//! The report is stored next to the item output, a single item can be explained with
+//! `--explain <name>`.
+
+use std::fmt::{self, Display};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::osrsbox::ItemProperties;
+
+/// The stage which removed or transformed an item.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "fate", rename_all = "snake_case")]
+pub enum Fate {
+    /// Marked as duplicate by OSRSBox.
+    Duplicate,
+    /// Not equippable.
+    NotEquipment,
+    /// No positive stats.
+    NoPositiveStats,
+    /// Item name in the name filter.
+    NameFilter {
+        /// Location of the rule.
+        origin: String,
+    },
+    /// Wiki name in the wiki name filter.
+    WikiNameFilter {
+        /// Location of the rule.
+        origin: String,
+    },
+    /// Filtered suffix of the item name.
+    Suffix {
+        /// The suffix.
+        suffix: String,
+    },
+    /// Filtered suffix of the wiki name.
+    WikiSuffix {
+        /// The suffix.
+        suffix: String,
+    },
+    /// Folded into an aggregated item.
+    Folded {
+        /// Wiki name of the aggregation target.
+        target: String,
+        /// Location of the rule.
+        origin: String,
+    },
+    /// Conversion failed.
+    Error {
+        /// Kind of the error, see [`ConversionError::kind`](crate::osrsbox::ConversionError::kind).
+        kind: String,
+        /// The error message.
+        message: String,
+    },
+    /// Kept.
+    Kept {
+        /// Name of the item in the output.
+        output_name: String,
+    },
+    /// Kept as stand-in for other items.
+    Aggregated {
+        /// Name of the item in the output.
+        output_name: String,
+        /// Ids of the items folded into this one.
+        folded: Vec<u32>,
+    },
+}
+
+impl Fate {
+    /// Short name of the fate, as used in the report.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Duplicate => "duplicate",
+            Self::NotEquipment => "not_equipment",
+            Self::NoPositiveStats => "no_positive_stats",
+            Self::NameFilter { .. } => "name_filter",
+            Self::WikiNameFilter { .. } => "wiki_name_filter",
+            Self::Suffix { .. } => "suffix",
+            Self::WikiSuffix { .. } => "wiki_suffix",
+            Self::Folded { .. } => "folded",
+            Self::Error { .. } => "error",
+            Self::Kept { .. } => "kept",
+            Self::Aggregated { .. } => "aggregated",
+        }
+    }
+
+    /// Returns `true` if the item is part of the output.
+    #[must_use]
+    pub fn is_kept(&self) -> bool {
+        matches!(self, Self::Kept { .. } | Self::Aggregated { .. })
+    }
+}
+
+impl Display for Fate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate => write!(f, "removed as duplicate"),
+            Self::NotEquipment => write!(f, "removed as it is not equipment"),
+            Self::NoPositiveStats => write!(f, "removed as it has no positive stats"),
+            Self::NameFilter { origin } => write!(f, "removed by name filter ({})", origin),
+            Self::WikiNameFilter { origin } => {
+                write!(f, "removed by wiki name filter ({})", origin)
+            }
+            Self::Suffix { suffix } => write!(f, "removed by suffix {}", suffix),
+            Self::WikiSuffix { suffix } => write!(f, "removed by wiki suffix {}", suffix),
+            Self::Folded { target, origin } => {
+                write!(f, "aggregated into {} ({})", target, origin)
+            }
+            Self::Error { message, .. } => write!(f, "removed by error: {}", message),
+            Self::Kept { output_name } => write!(f, "kept as {}", output_name),
+            Self::Aggregated {
+                output_name,
+                folded,
+            } => write!(
+                f,
+                "kept as {}, aggregating {} items",
+                output_name,
+                folded.len()
+            ),
+        }
+    }
+}
+
+/// The fate of one source item.
+#[derive(Debug, Clone, Serialize)]
+pub struct Trace {
+    /// OSRSBox item id.
+    pub id: u32,
+    /// OSRSBox item name.
+    pub name: String,
+    /// OSRSBox wiki name.
+    pub wiki_name: Option<String>,
+    /// What happened to the item.
+    #[serde(flatten)]
+    pub fate: Fate,
+}
+
+impl Trace {
+    /// Create the trace of `item`.
+    #[must_use]
+    pub fn new(item: &ItemProperties, fate: Fate) -> Self {
+        Self {
+            id: item.id,
+            name: item.name.clone(),
+            wiki_name: item.wiki_name.clone(),
+            fate,
+        }
+    }
+
+    /// Returns `true` if the name, wiki name or output name equals `name` (ignoring case).
+    #[must_use]
+    pub fn matches(&self, name: &str) -> bool {
+        let output_name = match &self.fate {
+            Fate::Kept { output_name } | Fate::Aggregated { output_name, .. } => Some(output_name),
+            _ => None,
+        };
+
+        std::iter::once(&self.name)
+            .chain(&self.wiki_name)
+            .chain(output_name)
+            .any(|n| n.eq_ignore_ascii_case(name))
+    }
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (id {}", self.name, self.id)?;
+        if let Some(wiki_name) = &self.wiki_name {
+            write!(f, ", wiki name {}", wiki_name)?;
+        }
+        write!(f, "): {}", self.fate)
+    }
+}
+
+/// A field value of an item source replaced by a source with higher precedence, see
+/// [source](crate::source).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Conflict {
+    /// Item id.
+    pub id: u32,
+    /// Name of the merged item, unless it is invalid.
+    pub name: Option<String>,
+    /// Path of the field, e.g. `equipment.prayer`.
+    pub field: String,
+    /// Name or location of the source of the new value.
+    pub source: String,
+    /// The new value.
+    pub value: Value,
+    /// Name or location of the source of the replaced value.
+    pub previous_source: String,
+    /// The replaced value.
+    pub previous: Value,
+}
+
+impl Conflict {
+    /// Returns `true` if the item name equals `name` (ignoring case).
+    #[must_use]
+    pub fn matches(&self, name: &str) -> bool {
+        self.name
+            .as_ref()
+            .is_some_and(|n| n.eq_ignore_ascii_case(name))
+    }
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (id {}): {} = {} ({}) replaces {} ({})",
+            self.name.as_deref().unwrap_or("?"),
+            self.id,
+            self.field,
+            self.value,
+            self.source,
+            self.previous,
+            self.previous_source
+        )
+    }
+}
+
+/// Traces of all source items, conflicts between the item sources and the errors found while
+/// processing them.
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    errors: Vec<String>,
+    conflicts: Vec<Conflict>,
+    #[serde(rename = "items")]
+    traces: Vec<Trace>,
+}
+
+impl Report {
+    /// Record the fate of an item.
+    pub fn push(&mut self, trace: Trace) {
+        self.traces.push(trace);
+    }
+
+    /// Record errors, e.g. leftover names in the item lists.
+    pub fn extend_errors(&mut self, errors: impl IntoIterator<Item = String>) {
+        self.errors.extend(errors);
+    }
+
+    /// All errors.
+    #[must_use]
+    pub fn errors(&self) -> &[String] {
+        &self.errors
+    }
+
+    /// Record conflicts between item sources.
+    pub fn extend_conflicts(&mut self, conflicts: impl IntoIterator<Item = Conflict>) {
+        self.conflicts.extend(conflicts);
+    }
+
+    /// All conflicts between item sources.
+    #[must_use]
+    pub fn conflicts(&self) -> &[Conflict] {
+        &self.conflicts
+    }
+
+    /// All traces.
+    pub fn iter(&self) -> impl Iterator<Item = &Trace> {
+        self.traces.iter()
+    }
+
+    /// Traces matching `name`, see [`Trace::matches`].
+    pub fn find<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Trace> + 'a {
+        self.traces.iter().filter(move |t| t.matches(name))
+    }
+
+    /// Conflicts of items named `name`, see [`Conflict::matches`].
+    pub fn find_conflicts<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Conflict> + 'a {
+        self.conflicts.iter().filter(move |c| c.matches(name))
+    }
+
+    /// Serialize the report as JSON, with errors sorted by message and conflicts and traces
+    /// sorted by item id.
+    ///
+    /// # Panics
+    ///
+    /// Never.
+    #[must_use]
+    pub fn to_json(&mut self) -> String {
+        self.errors.sort_unstable();
+        self.conflicts.sort_by_key(|c| c.id);
+        self.traces.sort_unstable_by_key(|t| t.id);
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report() {
+        let trace = |id: u32, name: &str, wiki_name: Option<&str>, fate| Trace {
+            id,
+            name: name.to_string(),
+            wiki_name: wiki_name.map(str::to_string),
+            fate,
+        };
+        let mut report = Report {
+            errors: vec!["Missed name filter: Rune dagger(p++)".to_string()],
+            conflicts: vec![Conflict {
+                id: 3,
+                name: Some("Rune dagger(p)".to_string()),
+                field: "weight".to_string(),
+                source: "overrides.toml:4".to_string(),
+                value: 0.5.into(),
+                previous_source: "osrsbox".to_string(),
+                previous: 0.45.into(),
+            }],
+            traces: vec![
+                trace(
+                    2,
+                    "Zamorak platebody",
+                    Some("Zamorak platebody"),
+                    Fate::Folded {
+                        target: "Saradomin platebody".to_string(),
+                        origin: "rules.toml:1".to_string(),
+                    },
+                ),
+                trace(
+                    1,
+                    "Saradomin platebody",
+                    Some("Saradomin platebody"),
+                    Fate::Aggregated {
+                        output_name: "God platebody".to_string(),
+                        folded: vec![2],
+                    },
+                ),
+                trace(
+                    3,
+                    "Rune dagger(p)",
+                    None,
+                    Fate::Suffix {
+                        suffix: "(p)".to_string(),
+                    },
+                ),
+            ],
+        };
+
+        let found: Vec<_> = report.find("god PLATEBODY").map(|t| t.id).collect();
+        assert_eq!(found, [1]);
+        assert_eq!(
+            report.find("Zamorak platebody").next().unwrap().to_string(),
+            "Zamorak platebody (id 2, wiki name Zamorak platebody): aggregated into Saradomin \
+             platebody (rules.toml:1)"
+        );
+
+        assert_eq!(
+            report
+                .find_conflicts("rune dagger(p)")
+                .next()
+                .unwrap()
+                .to_string(),
+            "Rune dagger(p) (id 3): weight = 0.5 (overrides.toml:4) replaces 0.45 (osrsbox)"
+        );
+
+        assert!(report.traces[1].fate.is_kept());
+        assert!(!report.traces[2].fate.is_kept());
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["errors"][0], report.errors()[0]);
+        assert_eq!(json["conflicts"][0]["field"], "weight");
+        assert_eq!(json["items"][0]["id"], 1);
+        assert_eq!(json["items"][0]["fate"], "aggregated");
+        assert_eq!(json["items"][0]["fate"], report.traces[0].fate.kind());
+        assert_eq!(json["items"][0]["name"], "Saradomin platebody");
+        assert_eq!(json["items"][0]["output_name"], "God platebody");
+        assert_eq!(json["items"][2]["fate"], "suffix");
+        assert_eq!(json["items"][2]["suffix"], "(p)");
+    }
+}
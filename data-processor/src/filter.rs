@@ -1,108 +1,123 @@
 //! Filters for unnecessary items.
+//!
+//! The filtered names and suffixes are listed in `rules.toml` (see [rules](crate::rules)).
 
-pub mod barrows;
-pub mod charges;
-pub mod cosmetic;
-pub mod dmm;
-pub mod fire_arrow;
-pub mod heraldic;
-pub mod misc;
-pub mod poison;
-pub mod suffixes;
+use std::collections::{HashMap, HashSet};
 
-use std::{collections::HashSet, sync::Mutex};
-
-use lazy_static::lazy_static;
-
-use crate::aggregate;
+use crate::aggregate::Aggregator;
+use crate::explain::Fate;
 use crate::osrsbox::ItemProperties;
+use crate::rules::{Origin, Rules};
+
+/// State of the filters during one run of the pipeline.
+#[derive(Debug, Clone, Default)]
+pub struct Filter {
+    /// Item names to be removed, with the rule they originate from.
+    names: HashMap<String, Origin>,
+    /// Wiki item names to be removed, with the rule they originate from.
+    wiki_names: HashMap<String, Origin>,
+    /// Suffixes (after the last space) of item names to be removed.
+    suffixes: HashSet<String>,
+    /// Suffixes (after the last ` (`) of wiki names to be removed.
+    wiki_suffixes: HashSet<String>,
+}
 
-lazy_static! {
-    /// Set of item names to be removed.
-    static ref NAME_SET: Mutex<HashSet<String>> = Mutex::new({
-        let mut set = HashSet::new();
-
-        barrows::add_names(&mut set);
-        charges::add_names(&mut set);
-        cosmetic::add_names(&mut set);
-        dmm::add_names(&mut set);
-        fire_arrow::add_names(&mut set);
-        misc::add_names(&mut set);
-        poison::add_names(&mut set);
+impl Filter {
+    /// Create a filter for the filters of `rules`.
+    #[must_use]
+    pub fn new(rules: &Rules) -> Self {
+        Self {
+            names: rules.names.clone(),
+            wiki_names: rules.wiki_names.clone(),
+            suffixes: rules.suffixes.clone(),
+            wiki_suffixes: rules.wiki_suffixes.clone(),
+        }
+    }
 
-        set
-    });
+    /// Return the reason for removing the item, or `None` if no filter applies. Items which are
+    /// folded into an aggregated item are passed to `aggregator`.
+    #[must_use]
+    pub fn removal(&mut self, item: &ItemProperties, aggregator: &mut Aggregator) -> Option<Fate> {
+        // Remove duplicates
+        if item.duplicate {
+            return Some(Fate::Duplicate);
+        }
 
-    /// Set of wiki item names to be removed.
-    static ref WIKI_NAME_SET: Mutex<HashSet<String>> = Mutex::new({
-        let mut set = HashSet::new();
+        // Remove non-equipment and equipment with no positive stats
+        if let Some(equipment) = item.equipment.as_ref() {
+            if !equipment.has_positive() {
+                return Some(Fate::NoPositiveStats);
+            }
+        } else {
+            return Some(Fate::NotEquipment);
+        }
 
-        aggregate::add_filter_names(&mut set);
+        // Remove items on name set
+        if let Some(origin) = self.names.remove(&item.name) {
+            return Some(Fate::NameFilter {
+                origin: origin.to_string(),
+            });
+        }
 
-        charges::add_wiki_names(&mut set);
-        cosmetic::add_wiki_names(&mut set);
-        heraldic::add_wiki_names(&mut set);
+        // Remove items which are folded into an aggregated item
+        if let Some((target, origin)) = aggregator.fold(item) {
+            return Some(Fate::Folded {
+                target,
+                origin: origin.to_string(),
+            });
+        }
 
-        set
-    });
-}
+        // Remove items on wiki name set
+        if let Some(wiki_name) = item.wiki_name.as_ref() {
+            if let Some(origin) = self.wiki_names.remove(wiki_name) {
+                return Some(Fate::WikiNameFilter {
+                    origin: origin.to_string(),
+                });
+            }
+
+            if let Some(suffix) = self.filtered_wiki_suffix(wiki_name) {
+                return Some(Fate::WikiSuffix {
+                    suffix: suffix.to_string(),
+                });
+            }
+        }
 
-/// Return true if no filter applies for the item.
-///
-/// # Panics
-///
-/// When unable to acquire the global locks.
-#[must_use]
-pub fn keep(item: &ItemProperties) -> bool {
-    // Remove duplicates
-    if item.duplicate {
-        return false;
+        // Remove items with filtered suffix
+        self.filtered_suffix(&item.name).map(|suffix| Fate::Suffix {
+            suffix: suffix.to_string(),
+        })
     }
 
-    // Remove non-equipment and equipment with no positive stats
-    if let Some(equipment) = item.equipment.as_ref() {
-        if !equipment.has_positive() {
-            return false;
-        }
-    } else {
-        return false;
+    /// Returns the [suffix] (after the last space) of the name if it is filtered.
+    ///
+    /// [suffix]: https://oldschool.runescape.wiki/w/Suffixes
+    #[must_use]
+    pub fn filtered_suffix<'a>(&self, name: &'a str) -> Option<&'a str> {
+        let suffix = &name[(name.rfind(' ')? + 1)..];
+        self.suffixes.contains(suffix).then_some(suffix)
     }
 
-    // Remove items on name set
-    if NAME_SET.lock().unwrap().remove(&item.name) {
-        return false;
+    /// Returns the suffix of the wiki name of an item if it is filtered.
+    #[must_use]
+    pub fn filtered_wiki_suffix<'a>(&self, wiki_name: &'a str) -> Option<&'a str> {
+        let suffix = &wiki_name[(wiki_name.rfind(" (")? + 1)..];
+        self.wiki_suffixes.contains(suffix).then_some(suffix)
     }
 
-    // Remove items on wiki name set
-    if let Some(wiki_name) = item.wiki_name.as_ref() {
-        if WIKI_NAME_SET.lock().unwrap().remove(wiki_name) {
-            return false;
-        }
+    /// Return errors for leftover names in the item filter (probably typos), with the rule they
+    /// originate from.
+    #[must_use]
+    pub fn check(&self) -> Vec<String> {
+        let mut errors = Vec::new();
 
-        if suffixes::has_filtered_wiki_suffix(wiki_name) {
-            return false;
+        for (name, origin) in &self.names {
+            errors.push(format!("Missed name filter: {} ({})", name, origin));
         }
-    }
-
-    // Remove items with filtered suffix
-    if suffixes::has_filtered_suffix(&item.name) {
-        return false;
-    }
-
-    true
-}
 
-/// Print leftover names in the item filter (probably typos).
-///
-/// # Panics
-///
-/// When unable to acquire the global locks.
-pub fn check() {
-    for name in NAME_SET.lock().unwrap().iter() {
-        println!("Error: Missed name filter: {}", name);
-    }
+        for (name, origin) in &self.wiki_names {
+            errors.push(format!("Missed wiki name filter: {} ({})", name, origin));
+        }
 
-    for name in WIKI_NAME_SET.lock().unwrap().iter() {
-        println!("Error: Missed wiki name filter: {}", name);
+        errors
     }
 }
@@ -0,0 +1,155 @@
+//! Read generated item data files for inspection and comparison.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    fs::File,
+    io::Read,
+    path::Path,
+};
+
+use data::{Database, Header, Item};
+use serde_json::Value;
+use xz2::read::XzDecoder;
+
+/// Decompress and decode the item data file at `path`.
+///
+/// # Errors
+///
+/// If the file can not be read or is not a valid item data file.
+pub fn load(path: &Path) -> Result<(Header, Database), String> {
+    let file = File::open(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let mut bytes = Vec::new();
+    XzDecoder::new(file)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Database::decode(&bytes).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Print the header and the items of `database`, optionally only those named `name`.
+pub fn print(header: &Header, database: &Database, name: Option<&str>) {
+    println!("Schema version:  {}", header.schema_version);
+    println!("Build timestamp: {}", header.build_timestamp);
+    println!("Features:        {}", header.features);
+    println!("Content hash:    {:016x}", header.content_hash);
+    println!("Items:           {}", database.len());
+
+    let mut items: Vec<_> = database
+        .iter()
+        .filter(|item| name.is_none_or(|name| item.name.eq_ignore_ascii_case(name)))
+        .collect();
+    items.sort_unstable_by_key(|item| item.id);
+    for item in items {
+        println!("{:#?}", item);
+    }
+}
+
+/// Difference of one item between two databases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// Only in the new database.
+    Added(String),
+    /// Only in the old database.
+    Removed(String),
+    /// In both databases, with the names of the differing fields.
+    Changed(String, Vec<String>),
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added(name) => write!(f, "+ {}", name),
+            Self::Removed(name) => write!(f, "- {}", name),
+            Self::Changed(name, fields) => write!(f, "~ {} ({})", name, fields.join(", ")),
+        }
+    }
+}
+
+/// Compare two databases by item name.
+#[must_use]
+pub fn diff(old: &Database, new: &Database) -> Vec<Change> {
+    diff_values(&values(old), &values(new))
+}
+
+/// Items by name as JSON values, which allows comparing them field by field.
+fn values(database: &Database) -> BTreeMap<String, Value> {
+    database
+        .iter()
+        .map(|item: &Item| {
+            let value = serde_json::to_value(item).unwrap_or(Value::Null);
+            (item.name.clone(), value)
+        })
+        .collect()
+}
+
+fn diff_values(old: &BTreeMap<String, Value>, new: &BTreeMap<String, Value>) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    for (name, old_value) in old {
+        match new.get(name) {
+            None => changes.push(Change::Removed(name.clone())),
+            Some(new_value) if new_value != old_value => {
+                let fields = match (old_value, new_value) {
+                    (Value::Object(old_fields), Value::Object(new_fields)) => old_fields
+                        .iter()
+                        .filter(|(field, value)| new_fields.get(*field) != Some(value))
+                        .map(|(field, _)| field.clone())
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                changes.push(Change::Changed(name.clone(), fields));
+            }
+            Some(_) => {}
+        }
+    }
+
+    for name in new.keys().filter(|name| !old.contains_key(*name)) {
+        changes.push(Change::Added(name.clone()));
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn load() {
+        let path = std::env::temp_dir().join("data-processor-inspect-load.bin.xz");
+        crate::save(&path, &Database::default().encode(1234)).unwrap();
+        let (header, database) = super::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(header.build_timestamp, 1234);
+        assert!(database.is_empty());
+        assert!(super::load(&path).is_err());
+    }
+
+    #[test]
+    fn diff() {
+        let old = BTreeMap::from([
+            ("A".to_string(), json!({"id": 1, "weight": 1.0})),
+            ("B".to_string(), json!({"id": 2, "weight": 1.0})),
+            ("C".to_string(), json!({"id": 3, "weight": 1.0})),
+        ]);
+        let new = BTreeMap::from([
+            ("B".to_string(), json!({"id": 2, "weight": 2.0})),
+            ("C".to_string(), json!({"id": 3, "weight": 1.0})),
+            ("D".to_string(), json!({"id": 4, "weight": 1.0})),
+        ]);
+
+        let changes = diff_values(&old, &new);
+        assert_eq!(
+            changes,
+            [
+                Change::Removed("A".to_string()),
+                Change::Changed("B".to_string(), vec!["weight".to_string()]),
+                Change::Added("D".to_string()),
+            ]
+        );
+        assert_eq!(changes[1].to_string(), "~ B (weight)");
+    }
+}
@@ -0,0 +1,59 @@
+//! Performs the following tasks:
+//! 1. Download item and monster data from the [OSRSBox](https://www.osrsbox.com/) static JSON API
+//!    and merge the items with an optional OSRS Wiki export and manual overrides (see [source])
+//! 2. Filter the items
+//!     - Duplicates
+//!     - Non-equippable items
+//!     - Items with no positive stats
+//!     - Others (see `rules.toml` and [rules])
+//! 3. Aggregate similar items (missed ones are found by [variants])
+//! 4. Append additional data, including Grand Exchange prices (see [prices])
+//! 5. Project to a more concise set of data-points
+//! 6. Store in the compact bincode format, preceded by a versioned [`Header`](data::Header)
+//! 7. Compress using the XZ algorithm
+//!
+//! Steps 2 to 5 are run by a [`Pipeline`](pipeline::Pipeline), which owns all filter, aggregation
+//! and mapping tables and can be run repeatedly, e.g. for different [rules] profiles. The fate of
+//! every source item is recorded in a [`Report`](explain::Report).
+//!
+//! Monsters are filtered and merged (see [monsters]).
+//!
+//! Data files are only rebuilt if the hashes of their inputs changed (see [manifest]).
+
+#![deny(missing_docs)]
+
+pub mod aggregate;
+pub mod explain;
+pub mod filter;
+pub mod inspect;
+pub mod manifest;
+pub mod map;
+pub mod monsters;
+pub mod osrsbox;
+pub mod pipeline;
+pub mod prices;
+pub mod rules;
+pub mod source;
+pub mod variants;
+
+use std::{
+    fs::File,
+    io::{self, Write},
+    path::Path,
+};
+
+use xz2::write::XzEncoder;
+
+/// Compress `data` and write it to `output_file`.
+///
+/// # Errors
+///
+/// If writing fails.
+pub fn save(output_file: &Path, data: &[u8]) -> Result<(), String> {
+    let write = || {
+        let mut encoder = XzEncoder::new(File::create(output_file)?, 9);
+        encoder.write_all(data)?;
+        encoder.finish().map(drop)
+    };
+    write().map_err(|e: io::Error| format!("{}: {}", output_file.display(), e))
+}
@@ -1,48 +1,66 @@
-//! Performs the following tasks:
-//! 1. Download item data from the [OSRSBox](https://www.osrsbox.com/) static JSON API
-//! 2. Filter the items
-//!     - Duplicates
-//!     - Non-equippable items
-//!     - Items with no positive stats
-//!     - Others (see submodules of [filter])
-//! 3. Aggregate similar items
-//! 4. Append additional data
-//! 5. Project to a more concise set of data-points
-//! 6. Store in the compact bincode format
-//! 7. Compress using the XZ algorithm
+//! Command line interface of the [`data_processor`] library.
 //!
-//! The final product is stored in `data/items.bin.xz`.
+//! The item data is stored in `dist/items.bin.xz`. The fate of every source item is stored in
+//! `dist/items-explain.json`, `explain <name>` prints the fate of matching items (see
+//! [`explain`](data_processor::explain)). Monsters are stored in `dist/monsters.bin.xz` the same
+//! way. `dist/manifest.json` records the hashes of all inputs (see
+//! [`manifest`](data_processor::manifest)), so unchanged data is not rebuilt.
+//!
+//! See [cli] for the available subcommands and options. The exit code is non-zero if any errors
+//! were found.
 
 #![deny(missing_docs)]
 
-pub mod aggregate;
-pub mod filter;
-pub mod map;
-pub mod osrsbox;
+pub mod cli;
 
 use std::{
-    collections::HashMap,
-    env,
+    collections::{BTreeMap, HashMap},
     fs::{self, File},
     io::{self, Read, Write},
-    time::Instant,
+    path::Path,
+    process::ExitCode,
+    sync::atomic::{AtomicU8, Ordering},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use clap::Parser;
+use data::{Database, Features, Monster, MonsterDatabase};
+use data_processor::{
+    explain::{Fate, Report},
+    inspect,
+    manifest::{self, Inputs},
+    map, monsters,
+    osrsbox::{ConversionError, MonsterProperties},
+    pipeline::Pipeline,
+    prices::Prices,
+    rules::Rules,
+    save,
+    source::{ItemSource, OsrsBox, Overrides, WikiExport},
+    variants,
 };
+use serde::de::DeserializeOwned;
 
-use data::Database;
-use xz2::write::XzEncoder;
+use cli::{BuildArgs, Cli, Command, Paths, Profile};
 
-use osrsbox::ItemProperties;
+/// Output level, see [`Cli::verbosity`].
+static VERBOSITY: AtomicU8 = AtomicU8::new(1);
 
-const DATA_URL: &str = "https://www.osrsbox.com/osrsbox-db/items-complete.json";
-const CACHE_FILE: &str = "data/items-complete.json";
-const OUTPUT_FILE: &str = "dist/items.bin.xz";
+/// Output level, see [`Cli::verbosity`].
+pub fn verbosity() -> u8 {
+    VERBOSITY.load(Ordering::Relaxed)
+}
 
-/// Print command name, time command execution and print timing.
+/// Print command name, time command execution and print timing. Only the result is returned when
+/// quiet.
 ///
 /// # Panics
 ///
 /// If printing to stdout fails.
 pub fn measure<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    if verbosity() == 0 {
+        return f();
+    }
+
     print!("{:30}", format!("{}...", name));
     io::stdout().flush().unwrap();
 
@@ -58,106 +76,430 @@ pub fn measure<T>(name: &str, f: impl FnOnce() -> T) -> T {
     result
 }
 
-/// Get the data from `items-complete.json`. Will look for `data/items-complete.json`, before
-/// downloading from [OSRSBox](https://www.osrsbox.com/).
+/// Get the data from `cache_file`, before downloading from `url` as a fallback.
 ///
-/// # Panics
+/// # Errors
 ///
-/// When one of the required operations fails.
-#[must_use]
-pub fn get_data() -> HashMap<String, ItemProperties> {
-    if let Ok(mut input_file) = File::open(CACHE_FILE) {
+/// When reading, downloading or parsing fails.
+pub fn get_data<T: DeserializeOwned>(
+    url: &str,
+    cache_file: &Path,
+) -> Result<HashMap<String, T>, String> {
+    if let Ok(mut input_file) = File::open(cache_file) {
         measure("Parsing file", || {
             // Using serde_json::from_reader is slower than this
             // (see https://github.com/serde-rs/json/issues/160)
             let mut s = String::new();
-            input_file.read_to_string(&mut s).unwrap();
-            serde_json::from_str(&s).unwrap()
+            input_file
+                .read_to_string(&mut s)
+                .map_err(|e| format!("{}: {}", cache_file.display(), e))?;
+            serde_json::from_str(&s).map_err(|e| format!("{}: {}", cache_file.display(), e))
         })
     } else {
         measure("Downloading & parsing", || {
-            let response = reqwest::blocking::get(DATA_URL).unwrap();
-            response.json().unwrap()
+            reqwest::blocking::get(url)
+                .and_then(reqwest::blocking::Response::json)
+                .map_err(|e| format!("{}: {}", url, e))
         })
     }
 }
 
-/// Save a local copy of the item data to reduce downloads during development.
+/// Save a local copy of the data at `url` to reduce downloads during development.
 ///
-/// # Panics
+/// # Errors
 ///
-/// If one of the required operations fails.
-pub fn cache_data() {
-    let response = reqwest::blocking::get(DATA_URL).unwrap();
-    let mut file = File::create(CACHE_FILE).unwrap();
-    io::copy(&mut response.bytes().unwrap().as_ref(), &mut file).unwrap();
+/// If downloading or writing fails.
+pub fn cache_data(url: &str, cache_file: &Path) -> Result<(), String> {
+    let bytes = reqwest::blocking::get(url)
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .and_then(reqwest::blocking::Response::bytes)
+        .map_err(|e| format!("{}: {}", url, e))?;
+    fs::write(cache_file, bytes).map_err(|e| format!("{}: {}", cache_file.display(), e))
 }
 
-/// Check whether the output file has to be regenerated based on file modification dates.
+/// Save a local copy of the Grand Exchange prices. The prices API requires a descriptive user
+/// agent.
 ///
 /// # Errors
 ///
-/// IO errors from reading file metadata.
-pub fn out_of_date() -> io::Result<bool> {
-    let output_date = fs::metadata(OUTPUT_FILE)?.modified()?;
-    let input_date = fs::metadata(CACHE_FILE)?.modified()?;
+/// If downloading or writing fails.
+pub fn cache_prices(url: &str, cache_file: &Path) -> Result<(), String> {
+    let bytes = reqwest::blocking::Client::builder()
+        .user_agent("osrs-equipment-tools data-processor")
+        .build()
+        .and_then(|client| client.get(url).send())
+        .and_then(reqwest::blocking::Response::error_for_status)
+        .and_then(reqwest::blocking::Response::bytes)
+        .map_err(|e| format!("{}: {}", url, e))?;
+    fs::write(cache_file, bytes).map_err(|e| format!("{}: {}", cache_file.display(), e))
+}
 
-    if input_date > output_date {
-        return Ok(true);
+/// Read the Grand Exchange prices from `cache_file`, if it exists.
+///
+/// # Errors
+///
+/// If the file exists but can not be read or parsed.
+pub fn get_prices(cache_file: &Path) -> Result<Option<Prices>, String> {
+    match fs::read_to_string(cache_file) {
+        Ok(s) => serde_json::from_str(&s)
+            .map(Some)
+            .map_err(|e| format!("{}: {}", cache_file.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("{}: {}", cache_file.display(), e)),
     }
+}
+
+fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Processed items, with the fate of every source item and the errors found on the way.
+struct ItemOutput {
+    items: Database,
+    report: Report,
+}
 
-    let exec = env::args()
-        .next()
-        .ok_or_else(|| io::Error::new(io::ErrorKind::Other, ""))?;
-    let exec_date = fs::metadata(exec)?.modified()?;
+fn process_items(paths: &Paths) -> Result<ItemOutput, String> {
+    let rules = Rules::load(&paths.rules, paths.profile == Profile::Trailblazer)?;
 
-    if exec_date > output_date {
-        return Ok(true);
+    if !paths.input.exists() {
+        if let Some(dir) = paths.input.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        measure("Downloading", || cache_data(&paths.data_url, &paths.input))?;
     }
 
-    Ok(false)
-}
+    let osrsbox = OsrsBox::new(&paths.input);
+    let wiki = paths.wiki_input.as_deref().map(WikiExport::new);
+    let overrides = Overrides::new(&paths.overrides);
+    let mut sources: Vec<&dyn ItemSource> = vec![&osrsbox];
+    if let Some(wiki) = &wiki {
+        sources.push(wiki);
+    }
+    sources.push(&overrides);
 
-#[doc(hidden)]
-fn main() {
-    if env::args().any(|arg| arg == "--cache") {
-        measure("Downloading", cache_data);
-        return;
+    let mut pipeline = Pipeline::new(&rules);
+    match measure("Parsing prices", || get_prices(&paths.price_input))? {
+        Some(prices) => pipeline = pipeline.with_prices(prices),
+        None if verbosity() > 0 => println!("No price data, run with --prices to download"),
+        None => {}
     }
 
-    if let Ok(false) = out_of_date() {
-        println!("Nothing to do...");
-        return;
+    let (items, report) = measure("Processing", || pipeline.process_sources(&sources))?;
+    if verbosity() > 0 {
+        println!("{:10} Source items", report.iter().count());
+        println!("{:10} Conflicts", report.conflicts().len());
+        println!("{:10} Items", items.len());
     }
 
-    let data = get_data();
-    println!("{:10} Items", data.len());
+    Ok(ItemOutput { items, report })
+}
+
+/// Processed monsters, with the monsters which failed to convert.
+struct MonsterOutput {
+    monsters: MonsterDatabase,
+    errors: Vec<(String, ConversionError)>,
+}
+
+fn process_monsters(paths: &Paths) -> Result<MonsterOutput, String> {
+    let data: HashMap<String, MonsterProperties> =
+        get_data(&paths.monster_data_url, &paths.monster_input)?;
+    if verbosity() > 0 {
+        println!("{:10} Monsters", data.len());
+    }
 
     let mut errors = Vec::new();
-    let items: Database = measure("Filtering & converting", || {
-        data.into_iter()
-            .filter_map(|i| {
-                let name = i.1.wiki_name.clone().unwrap_or_else(|| i.1.name.clone());
-                if filter::keep(&i.1) {
-                    map::map(i.1)
-                        .map_err(|e| errors.push(format!("{}: {}", name, e)))
-                        .ok()
-                } else {
-                    None
-                }
-            })
-            .collect()
+    let monsters = measure("Filtering & merging", || {
+        monsters::merge(data.into_values().filter(monsters::keep).filter_map(|m| {
+            let name = m.name.clone();
+            Monster::try_from(m)
+                .map_err(|e| errors.push((name, e)))
+                .ok()
+        }))
     });
-    println!("{:10} Items", items.len());
+    if verbosity() > 0 {
+        println!("{:10} Monsters", monsters.len());
+    }
+
+    Ok(MonsterOutput { monsters, errors })
+}
 
-    for e in errors {
-        println!("Error: {}", e);
+/// Errors of a run, with the number of conversion errors by kind.
+#[derive(Default)]
+struct Errors {
+    messages: Vec<String>,
+    kinds: BTreeMap<String, usize>,
+}
+
+impl Errors {
+    fn items(&mut self, report: &Report) {
+        self.messages.extend(report.errors().iter().cloned());
+        for trace in report.iter() {
+            if let Fate::Error { kind, .. } = &trace.fate {
+                *self.kinds.entry(kind.clone()).or_default() += 1;
+            }
+        }
     }
-    filter::check();
-    map::check();
 
-    measure("Saving", || {
-        let output_file = File::create(OUTPUT_FILE).unwrap();
-        bincode::serialize_into(XzEncoder::new(output_file, 9), &items).unwrap();
-    });
+    fn monsters(&mut self, errors: &[(String, ConversionError)]) {
+        for (name, e) in errors {
+            self.messages.push(format!("Monster {}: {}", name, e));
+            *self.kinds.entry(e.kind().to_string()).or_default() += 1;
+        }
+    }
+
+    /// Print all errors.
+    fn print(&self) {
+        for e in &self.messages {
+            println!("Error: {}", e);
+        }
+    }
+
+    /// Print all errors, sorted, followed by the number of errors by kind.
+    fn print_summary(&self) {
+        let mut messages: Vec<_> = self.messages.iter().collect();
+        messages.sort_unstable();
+        for e in messages {
+            println!("Error: {}", e);
+        }
+
+        println!("{:10} Errors", self.messages.len());
+        let conversion: usize = self.kinds.values().sum();
+        for (kind, count) in &self.kinds {
+            println!("{:10} {}", count, kind);
+        }
+        println!("{:10} other", self.messages.len() - conversion);
+    }
+}
+
+/// Print the traces of the report, depending on the verbosity: a summary of the fates with `-v`,
+/// every removed item and every conflict between item sources with `-vv`.
+fn print_report(report: &Report) {
+    if verbosity() >= 2 {
+        let mut counts = HashMap::new();
+        for trace in report.iter() {
+            *counts.entry(trace.fate.kind()).or_insert(0) += 1;
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort_unstable();
+        for (kind, count) in counts {
+            println!("{:10} {}", count, kind);
+        }
+    }
+
+    if verbosity() >= 3 {
+        for trace in report.iter().filter(|t| !t.fate.is_kept()) {
+            println!("{}", trace);
+        }
+        for conflict in report.conflicts() {
+            println!("{}", conflict);
+        }
+    }
+}
+
+/// Hashes of all inputs of the item data, see [`manifest`].
+fn item_inputs(paths: &Paths) -> Result<Inputs, String> {
+    let mut inputs = Inputs::new(Features::current())
+        .file("items", &paths.input)?
+        .file("prices", &paths.price_input)?
+        .file("rules.toml", &paths.rules)?
+        .file("overrides.toml", &paths.overrides)?;
+    if let Some(wiki_input) = &paths.wiki_input {
+        inputs = inputs.file("wiki", wiki_input)?;
+    }
+    for (name, data) in map::DATA_FILES {
+        inputs = inputs.data(name, data.as_bytes());
+    }
+    Ok(inputs)
+}
+
+/// Hashes of all inputs of the monster data, see [`manifest`].
+fn monster_inputs(paths: &Paths) -> Result<Inputs, String> {
+    Inputs::new(Features::current()).file("monsters", &paths.monster_input)
+}
+
+/// Generate the item and monster data if their inputs changed. Returns the number of errors.
+///
+/// In strict mode, nothing is written if there are any errors, which are summarized at the end.
+fn build(args: &BuildArgs) -> Result<usize, String> {
+    let paths = &args.paths;
+    paths.profile.check()?;
+    fs::create_dir_all(&paths.output_dir)
+        .map_err(|e| format!("{}: {}", paths.output_dir.display(), e))?;
+
+    if args.prices {
+        measure("Downloading prices", || {
+            cache_prices(&paths.price_data_url, &paths.price_input)
+        })?;
+    }
+
+    let manifest_file = paths.manifest();
+    let mut manifest = manifest::load(&manifest_file);
+    let mut errors = Errors::default();
+
+    let item_file = paths.items();
+    let mut items = None;
+    let inputs = measure("Hashing inputs", || item_inputs(paths))?;
+    if !args.force && inputs.is_built(manifest.items.as_ref(), &item_file) {
+        if verbosity() > 0 {
+            println!("Items up to date...");
+        }
+    } else {
+        let output = process_items(paths)?;
+        errors.items(&output.report);
+        print_report(&output.report);
+        items = Some(output);
+    }
+
+    let monster_file = paths.monsters();
+    let mut monsters = None;
+    let inputs = monster_inputs(paths)?;
+    if !args.force && inputs.is_built(manifest.monsters.as_ref(), &monster_file) {
+        if verbosity() > 0 {
+            println!("Monsters up to date...");
+        }
+    } else {
+        let output = process_monsters(paths)?;
+        errors.monsters(&output.errors);
+        monsters = Some(output.monsters);
+    }
+
+    if args.strict && !errors.messages.is_empty() {
+        errors.print_summary();
+        return Err(format!(
+            "{} errors in strict mode, nothing written",
+            errors.messages.len()
+        ));
+    }
+    errors.print();
+
+    if let Some(mut output) = items {
+        measure("Saving", || {
+            save(&item_file, &output.items.encode(timestamp()))
+        })?;
+        measure("Saving report", || {
+            let report = paths.report();
+            fs::write(&report, output.report.to_json())
+                .map_err(|e| format!("{}: {}", report.display(), e))
+        })?;
+        // Hashed again, the item data may have been downloaded while processing
+        manifest.items = Some(item_inputs(paths)?.data_file(&item_file)?);
+    }
+
+    if let Some(monsters) = monsters {
+        measure("Saving", || {
+            save(&monster_file, &monsters.encode(timestamp()))
+        })?;
+        manifest.monsters = Some(monster_inputs(paths)?.data_file(&monster_file)?);
+    }
+
+    manifest::save(&manifest, &manifest_file)?;
+
+    Ok(errors.messages.len())
+}
+
+/// Run the subcommand. Returns the number of errors.
+fn run(cli: Cli) -> Result<usize, String> {
+    match cli.command {
+        None => build(&cli.build),
+        Some(Command::Build(args)) => build(&args),
+        Some(Command::Cache { paths, prices }) => {
+            if let Some(dir) = paths.input.parent() {
+                fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+            }
+            measure("Downloading", || cache_data(&paths.data_url, &paths.input))?;
+            measure("Downloading monsters", || {
+                cache_data(&paths.monster_data_url, &paths.monster_input)
+            })?;
+            if prices {
+                measure("Downloading prices", || {
+                    cache_prices(&paths.price_data_url, &paths.price_input)
+                })?;
+            }
+            Ok(0)
+        }
+        Some(Command::Explain { paths, name }) => {
+            paths.profile.check()?;
+            let output = process_items(&paths)?;
+            let mut found = false;
+            for trace in output.report.find(&name) {
+                println!("{}", trace);
+                found = true;
+            }
+            for conflict in output.report.find_conflicts(&name) {
+                println!("{}", conflict);
+            }
+            if found {
+                Ok(0)
+            } else {
+                Err(format!("no item named {}", name))
+            }
+        }
+        Some(Command::Variants { paths }) => {
+            paths.profile.check()?;
+            let output = process_items(&paths)?;
+            let clusters = variants::clusters(&output.items, &output.report);
+            for cluster in &clusters {
+                println!("{}\n{}\n", cluster, cluster.suggestion());
+            }
+            if verbosity() > 0 {
+                println!("{} groups of identical items", clusters.len());
+            }
+            Ok(0)
+        }
+        Some(Command::Diff { old, new, paths }) => {
+            let new = new.unwrap_or_else(|| paths.items());
+            let (_, old) = inspect::load(&old)?;
+            let (_, new) = inspect::load(&new)?;
+            for change in inspect::diff(&old, &new) {
+                println!("{}", change);
+            }
+            Ok(0)
+        }
+        Some(Command::Inspect { file, name, paths }) => {
+            let file = file.unwrap_or_else(|| paths.items());
+            let (header, database) = inspect::load(&file)?;
+            inspect::print(&header, &database, name.as_deref());
+            Ok(0)
+        }
+        Some(Command::Validate { paths }) => {
+            paths.profile.check()?;
+            let output = process_items(&paths)?;
+            print_report(&output.report);
+            let monsters = process_monsters(&paths)?;
+
+            let mut errors = Errors::default();
+            errors.items(&output.report);
+            errors.monsters(&monsters.errors);
+            if errors.messages.is_empty() {
+                if verbosity() > 0 {
+                    println!("No errors");
+                }
+            } else {
+                errors.print_summary();
+            }
+            Ok(errors.messages.len())
+        }
+    }
+}
+
+#[doc(hidden)]
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    VERBOSITY.store(cli.verbosity(), Ordering::Relaxed);
+
+    match run(cli) {
+        Ok(0) => ExitCode::SUCCESS,
+        Ok(errors) => {
+            eprintln!("{} errors", errors);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
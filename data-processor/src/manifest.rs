@@ -0,0 +1,166 @@
+//! Incremental rebuilds based on content hashes.
+//!
+//! The [`Manifest`] records the hashes of all inputs of each data file. A data file is only
+//! rebuilt if an input changed or the file itself does not match its recorded hash.
+
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use data::{content_hash, DataFile, Features, Manifest, SCHEMA_VERSION};
+use lazy_static::lazy_static;
+
+lazy_static! {
+    /// Hash of the running executable, so any change to the processor causes a rebuild. If the
+    /// executable can not be read, the start time is used instead, so everything is rebuilt.
+    static ref PROCESSOR: String = env::current_exe()
+        .and_then(fs::read)
+        .map_or_else(
+            |_| {
+                let start = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_nanos());
+                format!("unknown-{}", start)
+            },
+            |bytes| hash(&bytes),
+        );
+}
+
+/// Hexadecimal [`content_hash`] of `bytes`.
+#[must_use]
+pub fn hash(bytes: &[u8]) -> String {
+    format!("{:016x}", content_hash(bytes))
+}
+
+/// Hashes and versions of the inputs of a data file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Inputs(BTreeMap<String, String>);
+
+impl Inputs {
+    /// Inputs with the hash of the processor executable, the schema version and `features`.
+    #[must_use]
+    pub fn new(features: Features) -> Self {
+        let mut inputs = BTreeMap::new();
+        inputs.insert("processor".to_string(), PROCESSOR.clone());
+        inputs.insert("schema".to_string(), SCHEMA_VERSION.to_string());
+        inputs.insert("features".to_string(), features.to_string());
+        Self(inputs)
+    }
+
+    /// Add the hash of `bytes` as `name`.
+    #[must_use]
+    pub fn data(mut self, name: &str, bytes: &[u8]) -> Self {
+        self.0.insert(name.to_string(), hash(bytes));
+        self
+    }
+
+    /// Add the hash of the file at `path` as `name`, or `missing` if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// If the file exists but can not be read.
+    pub fn file(mut self, name: &str, path: &Path) -> Result<Self, String> {
+        let value = match fs::read(path) {
+            Ok(bytes) => hash(&bytes),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => "missing".to_string(),
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        };
+        self.0.insert(name.to_string(), value);
+        Ok(self)
+    }
+
+    /// Returns `true` if `file` was built from these inputs and `output` still has the recorded
+    /// hash.
+    #[must_use]
+    pub fn is_built(&self, file: Option<&DataFile>, output: &Path) -> bool {
+        file.is_some_and(|file| {
+            file.inputs == self.0 && fs::read(output).is_ok_and(|bytes| hash(&bytes) == file.hash)
+        })
+    }
+
+    /// Record of the data file at `output`, built from these inputs.
+    ///
+    /// # Errors
+    ///
+    /// If the file can not be read.
+    pub fn data_file(self, output: &Path) -> Result<DataFile, String> {
+        let bytes = fs::read(output).map_err(|e| format!("{}: {}", output.display(), e))?;
+        Ok(DataFile {
+            inputs: self.0,
+            hash: hash(&bytes),
+        })
+    }
+}
+
+/// Read the manifest at `path`. A missing or invalid manifest is empty, so everything is rebuilt.
+#[must_use]
+pub fn load(path: &Path) -> Manifest {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+/// Write `manifest` to `path`.
+///
+/// # Errors
+///
+/// If writing fails.
+///
+/// # Panics
+///
+/// Never.
+pub fn save(manifest: &Manifest, path: &Path) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(manifest).unwrap();
+    fs::write(path, json).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebuild() {
+        let dir = std::env::temp_dir().join("data-processor-manifest");
+        fs::create_dir_all(&dir).unwrap();
+        let (input, output) = (dir.join("input.json"), dir.join("output.bin.xz"));
+        fs::write(&input, "[1]").unwrap();
+        fs::write(&output, "output").unwrap();
+
+        let inputs = || {
+            Inputs::new(Features::current())
+                .data("rules", b"rules")
+                .file("input", &input)
+                .unwrap()
+        };
+        assert!(!inputs().is_built(None, &output));
+        let executable = fs::read(env::current_exe().unwrap()).unwrap();
+        assert_eq!(inputs().0["processor"], hash(&executable));
+
+        let manifest = Manifest {
+            items: Some(inputs().data_file(&output).unwrap()),
+            monsters: None,
+        };
+        save(&manifest, &dir.join("manifest.json")).unwrap();
+        let manifest = load(&dir.join("manifest.json"));
+        let file = manifest.items.as_ref();
+        assert_eq!(file.unwrap().hash, hash(b"output"));
+        assert!(inputs().is_built(file, &output));
+
+        // Changed input
+        fs::write(&input, "[2]").unwrap();
+        assert!(!inputs().is_built(file, &output));
+        fs::write(&input, "[1]").unwrap();
+        assert!(!inputs().data("rules", b"new rules").is_built(file, &output));
+
+        // Modified or deleted output
+        fs::write(&output, "modified").unwrap();
+        assert!(!inputs().is_built(file, &output));
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(!inputs().is_built(file, &output));
+        assert_eq!(load(&dir.join("manifest.json")), Manifest::default());
+    }
+}
@@ -1,57 +1,112 @@
 //! Adjust item data and apply projection.
 
 mod clues;
+mod conditional;
+mod reclaim;
+mod requirements;
+mod sets;
+mod sources;
 
-#[cfg(feature = "trailblazer")]
-use std::sync::Mutex;
+use std::collections::HashMap;
 
-use data::Item;
-#[cfg(feature = "trailblazer")]
-use lazy_static::lazy_static;
+use data::{Clue, ConditionalBonus, Item, Requirement, Source};
 
-use crate::aggregate;
-use crate::osrsbox::ItemProperties;
+use crate::osrsbox::{ConversionError, ItemProperties};
+use crate::rules::Rules;
 
-#[cfg(feature = "trailblazer")]
-lazy_static! {
+/// Data files compiled into the mapping tables, by file name.
+pub const DATA_FILES: [(&str, &str); 3] = [
+    ("reclaim.json", reclaim::DATA),
+    ("requirements.json", requirements::DATA),
+    ("sources.json", sources::DATA),
+];
+
+/// Additional item data by item name. Entries are consumed when applied, so leftover names can be
+/// reported by [`Mapper::check`].
+#[derive(Debug, Clone)]
+pub struct Mapper {
+    clues: HashMap<String, Clue>,
+    bonuses: HashMap<String, Vec<ConditionalBonus>>,
+    requirements: HashMap<String, Vec<Requirement>>,
+    reclaim_costs: HashMap<String, u32>,
+    /// Names of untradeable items without a reclaim cost.
+    missing_reclaim_costs: Vec<String>,
+    sets: sets::Table,
+    sources: HashMap<String, Vec<Source>>,
     /// Map of item names to Trailblazer region expressions.
-    static ref TRAILBLAZER_MAP: Mutex<regions::data::ExprMap> =
-        Mutex::new(regions::data::create_map());
+    #[cfg(feature = "trailblazer")]
+    trailblazer: regions::data::ExprMap,
 }
 
-/// Apply all transformation methods.
-///
-/// # Errors
-///
-/// Never.
-pub fn map(mut item: ItemProperties) -> Result<Item, String> {
-    #[cfg(feature = "trailblazer")]
-    let trailblazer = TRAILBLAZER_MAP
-        .lock()
-        .unwrap()
-        .remove(item.wiki_name.as_ref().unwrap());
+impl Mapper {
+    /// Create a mapper with the tables of the data files and the armour sets of `rules`.
+    #[must_use]
+    pub fn new(rules: &Rules) -> Self {
+        Self {
+            clues: clues::table(),
+            bonuses: conditional::table(),
+            requirements: requirements::table(),
+            reclaim_costs: reclaim::table(),
+            missing_reclaim_costs: Vec::new(),
+            sets: sets::table(rules),
+            sources: sources::table(),
+            #[cfg(feature = "trailblazer")]
+            trailblazer: regions::data::create_map(),
+        }
+    }
 
-    aggregate::apply_aggregation(&mut item);
+    /// Convert the item and apply all transformation methods.
+    ///
+    /// # Errors
+    ///
+    /// If the item can not be converted.
+    pub fn map(&mut self, item: ItemProperties) -> Result<Item, ConversionError> {
+        #[cfg(feature = "trailblazer")]
+        let trailblazer = item
+            .wiki_name
+            .as_ref()
+            .and_then(|wiki_name| self.trailblazer.remove(wiki_name));
 
-    let mut item = item.into();
+        let mut item = Item::try_from(item)?;
 
-    clues::apply_value(&mut item);
+        clues::apply_value(&mut self.clues, &mut item);
+        conditional::apply_bonuses(&mut self.bonuses, &mut item);
+        requirements::apply_requirements(&mut self.requirements, &mut item);
+        reclaim::apply_cost(
+            &mut self.reclaim_costs,
+            &mut self.missing_reclaim_costs,
+            &mut item,
+        );
+        sets::apply_sets(&mut self.sets, &mut item);
+        sources::apply_sources(&mut self.sources, &mut item);
 
-    #[cfg(feature = "trailblazer")]
-    {
-        item.trailblazer = trailblazer;
+        #[cfg(feature = "trailblazer")]
+        {
+            item.trailblazer = trailblazer;
+        }
+
+        Ok(item)
     }
 
-    Ok(item)
-}
+    /// Return errors for leftover names in all item lists, including the Trailblazer item map
+    /// (probably typos), and for untradeable items without a reclaim cost.
+    #[must_use]
+    pub fn check(&self) -> Vec<String> {
+        let mut errors = clues::check(&self.clues);
+        errors.extend(conditional::check(&self.bonuses));
+        errors.extend(reclaim::check(
+            &self.reclaim_costs,
+            &self.missing_reclaim_costs,
+        ));
+        errors.extend(requirements::check(&self.requirements));
+        errors.extend(sets::check(&self.sets));
+        errors.extend(sources::check(&self.sources));
 
-/// Print leftover names in the Trailblazer item map (probably typos).
-pub fn check() {
-    aggregate::check();
-    clues::check();
+        #[cfg(feature = "trailblazer")]
+        for (name, _) in self.trailblazer.iter() {
+            errors.push(format!("Missed Trailblazer map: {}", name));
+        }
 
-    #[cfg(feature = "trailblazer")]
-    for (name, _) in TRAILBLAZER_MAP.lock().unwrap().iter() {
-        println!("Missed Trailblazer map: {}", name);
+        errors
     }
 }
@@ -1,13 +1,13 @@
 //! Adds clue tier data to items.
 
-use std::{collections::HashMap, sync::Mutex};
+use std::collections::HashMap;
 
 use data::{Clue, Item};
 use lazy_static::lazy_static;
 
 lazy_static! {
     /// Set of 3rd age item names.
-    static ref ITEM_NAMES: Mutex<HashMap<String, Clue>> = Mutex::new({
+    static ref ITEM_NAMES: HashMap<String, Clue> = {
         let mut map = HashMap::new();
 
         // Easy
@@ -90,17 +90,25 @@ lazy_static! {
         map.insert("3rd age axe".to_string(), Clue::Master{mega_rare: true});
 
         map
-    });
+    };
+}
+
+/// A copy of the table, which is consumed by [`apply_value`] and [`check`].
+#[must_use]
+pub fn table() -> HashMap<String, Clue> {
+    ITEM_NAMES.clone()
 }
 
 /// Applies `item.clue = <level>` for clue uniques.
-pub fn apply_value(item: &mut Item) {
-    item.attainability.clue = ITEM_NAMES.lock().unwrap().remove(&item.name);
+pub fn apply_value(table: &mut HashMap<String, Clue>, item: &mut Item) {
+    item.attainability.clue = table.remove(&item.name);
 }
 
-/// Print leftover names in the item list (probably typos).
-pub fn check() {
-    for (name, _) in ITEM_NAMES.lock().unwrap().iter() {
-        println!("Error: Missed clue data: {}", name);
-    }
+/// Return errors for leftover names in the item list (probably typos).
+#[must_use]
+pub fn check(table: &HashMap<String, Clue>) -> Vec<String> {
+    table
+        .keys()
+        .map(|name| format!("Missed clue data: {}", name))
+        .collect()
 }
@@ -0,0 +1,124 @@
+//! Adds bonuses which only apply against certain targets or in certain areas.
+
+use std::collections::HashMap;
+
+use data::{CombatClass, Condition, ConditionalBonus, Item, MonsterAttribute, Multiplier};
+use lazy_static::lazy_static;
+
+const UNDEAD: Condition = Condition::Attribute(MonsterAttribute::Undead);
+const DEMON: Condition = Condition::Attribute(MonsterAttribute::Demon);
+const DRAGON: Condition = Condition::Attribute(MonsterAttribute::Dragon);
+
+// Multipliers named after the bonus they add
+const ONE_SIXTH: Multiplier = Multiplier::new(7, 6);
+const ONE_FIFTH: Multiplier = Multiplier::new(6, 5);
+const HALF: Multiplier = Multiplier::new(3, 2);
+
+lazy_static! {
+    /// Conditional bonuses by item name.
+    static ref ITEM_BONUSES: HashMap<String, Vec<ConditionalBonus>> = {
+        let mut map = HashMap::new();
+
+        // Slayer helmets and black masks (charged variants are filtered, see rules.toml)
+        let slayer = vec![ConditionalBonus::new(Condition::SlayerTask, CombatClass::Melee, ONE_SIXTH)];
+        let slayer_imbued = vec![
+            ConditionalBonus::new(Condition::SlayerTask, CombatClass::Melee, ONE_SIXTH),
+            ConditionalBonus::new(Condition::SlayerTask, CombatClass::Ranged, Multiplier::new(23, 20)),
+            ConditionalBonus::new(Condition::SlayerTask, CombatClass::Magic, Multiplier::new(23, 20)),
+        ];
+        map.insert("Black mask".to_string(), slayer.clone());
+        map.insert("Slayer helmet".to_string(), slayer);
+        map.insert("Black mask (i)".to_string(), slayer_imbued.clone());
+        map.insert("Slayer helmet (i)".to_string(), slayer_imbued);
+
+        // Salve amulets
+        map.insert(
+            "Salve amulet".to_string(),
+            vec![ConditionalBonus::new(UNDEAD, CombatClass::Melee, ONE_SIXTH)],
+        );
+        map.insert(
+            "Salve amulet(e)".to_string(),
+            vec![ConditionalBonus::new(UNDEAD, CombatClass::Melee, ONE_FIFTH)],
+        );
+        map.insert(
+            "Salve amulet(i)".to_string(),
+            vec![
+                ConditionalBonus::new(UNDEAD, CombatClass::Melee, ONE_SIXTH),
+                ConditionalBonus::new(UNDEAD, CombatClass::Ranged, ONE_SIXTH),
+                ConditionalBonus::new(UNDEAD, CombatClass::Magic, Multiplier::new(23, 20)),
+            ],
+        );
+        map.insert(
+            "Salve amulet(ei)".to_string(),
+            vec![
+                ConditionalBonus::new(UNDEAD, CombatClass::Melee, ONE_FIFTH),
+                ConditionalBonus::new(UNDEAD, CombatClass::Ranged, ONE_FIFTH),
+                ConditionalBonus::new(UNDEAD, CombatClass::Magic, ONE_FIFTH),
+            ],
+        );
+
+        // Dragonbane weapons
+        map.insert(
+            "Dragon hunter lance".to_string(),
+            vec![ConditionalBonus::new(DRAGON, CombatClass::Melee, ONE_FIFTH)],
+        );
+        map.insert(
+            "Dragon hunter crossbow".to_string(),
+            vec![ConditionalBonus {
+                condition: DRAGON,
+                class: CombatClass::Ranged,
+                accuracy: Multiplier::new(13, 10),
+                damage: Multiplier::new(5, 4),
+            }],
+        );
+
+        // Demonbane weapons
+        map.insert(
+            "Arclight".to_string(),
+            vec![ConditionalBonus::new(DEMON, CombatClass::Melee, Multiplier::new(17, 10))],
+        );
+
+        // Wilderness weapons
+        map.insert(
+            "Craw's bow".to_string(),
+            vec![ConditionalBonus::new(Condition::Wilderness, CombatClass::Ranged, HALF)],
+        );
+        map.insert(
+            "Viggora's chainmace".to_string(),
+            vec![ConditionalBonus::new(Condition::Wilderness, CombatClass::Melee, HALF)],
+        );
+        map.insert(
+            "Thammaron's sceptre".to_string(),
+            vec![ConditionalBonus {
+                condition: Condition::Wilderness,
+                class: CombatClass::Magic,
+                accuracy: Multiplier::new(2, 1),
+                damage: Multiplier::new(5, 4),
+            }],
+        );
+
+        map
+    };
+}
+
+/// A copy of the table, which is consumed by [`apply_bonuses`] and [`check`].
+#[must_use]
+pub fn table() -> HashMap<String, Vec<ConditionalBonus>> {
+    ITEM_BONUSES.clone()
+}
+
+/// Applies the conditional bonuses of `item`.
+pub fn apply_bonuses(table: &mut HashMap<String, Vec<ConditionalBonus>>, item: &mut Item) {
+    if let Some(bonuses) = table.remove(&item.name) {
+        item.conditional_bonuses = bonuses;
+    }
+}
+
+/// Return errors for leftover names in the bonus list (probably typos).
+#[must_use]
+pub fn check(table: &HashMap<String, Vec<ConditionalBonus>>) -> Vec<String> {
+    table
+        .keys()
+        .map(|name| format!("Missed conditional bonus: {}", name))
+        .collect()
+}
@@ -0,0 +1,48 @@
+//! Adds the cost to reclaim untradeable items lost in the wilderness.
+//!
+//! The costs are maintained in `reclaim.json`, mapping item names to coins. Every untradeable
+//! item needs an entry, `0` if it can be reclaimed for free.
+
+use std::collections::HashMap;
+
+use data::Item;
+use lazy_static::lazy_static;
+
+/// Contents of `reclaim.json`, the reclaim costs by item name.
+pub const DATA: &str = include_str!("../../reclaim.json");
+
+lazy_static! {
+    /// Map of item names to reclaim costs.
+    static ref RECLAIM_COSTS: HashMap<String, u32> =
+        serde_json::from_str(DATA).expect("invalid reclaim.json");
+}
+
+/// A copy of the table, which is consumed by [`apply_cost`] and [`check`].
+#[must_use]
+pub fn table() -> HashMap<String, u32> {
+    RECLAIM_COSTS.clone()
+}
+
+/// Applies `item.price.reclaim = <cost>` for items listed in `reclaim.json`. The names of
+/// untradeable items without a cost are added to `missing`.
+pub fn apply_cost(table: &mut HashMap<String, u32>, missing: &mut Vec<String>, item: &mut Item) {
+    item.price.reclaim = table.remove(&item.name);
+    if item.price.reclaim.is_none() && !item.attainability.tradeable {
+        missing.push(item.name.clone());
+    }
+}
+
+/// Return errors for leftover names in the reclaim cost list (probably typos) and for `missing`
+/// costs of untradeable items.
+#[must_use]
+pub fn check(table: &HashMap<String, u32>, missing: &[String]) -> Vec<String> {
+    table
+        .keys()
+        .map(|name| format!("Missed reclaim cost: {}", name))
+        .chain(
+            missing
+                .iter()
+                .map(|name| format!("Missing reclaim cost of untradeable item: {}", name)),
+        )
+        .collect()
+}
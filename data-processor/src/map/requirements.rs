@@ -0,0 +1,57 @@
+//! Adds quest, diary and combat achievement requirements to items.
+//!
+//! The requirements are maintained in `requirements.json`, mapping item names to a list of
+//! additional [`Requirement`]s.
+
+use std::collections::HashMap;
+
+use data::{Item, Requirement};
+use lazy_static::lazy_static;
+
+/// Contents of `requirements.json`, the additional requirements by item name.
+pub const DATA: &str = include_str!("../../requirements.json");
+
+lazy_static! {
+    /// Map of item names to their additional requirements.
+    static ref ITEM_REQUIREMENTS: HashMap<String, Vec<Requirement>> =
+        serde_json::from_str(DATA).expect("invalid requirements.json");
+}
+
+/// A copy of the table, which is consumed by [`apply_requirements`] and [`check`].
+#[must_use]
+pub fn table() -> HashMap<String, Vec<Requirement>> {
+    ITEM_REQUIREMENTS.clone()
+}
+
+/// Appends the requirements listed in `requirements.json` to `item.requirements`.
+pub fn apply_requirements(table: &mut HashMap<String, Vec<Requirement>>, item: &mut Item) {
+    if let Some(requirements) = table.remove(&item.name) {
+        item.requirements.extend(requirements);
+    }
+}
+
+/// Return errors for leftover names in the requirement list (probably typos).
+#[must_use]
+pub fn check(table: &HashMap<String, Vec<Requirement>>) -> Vec<String> {
+    table
+        .keys()
+        .map(|name| format!("Missed requirement data: {}", name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use data::RequirementType;
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let requirements = table();
+        assert!(requirements.values().all(|r| !r.is_empty()));
+        assert!(matches!(
+            &requirements["Dragon scimitar"][0].requirement,
+            RequirementType::Quest(quest) if quest == "Monkey Madness I"
+        ));
+    }
+}
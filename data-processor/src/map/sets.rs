@@ -0,0 +1,34 @@
+//! Adds armour set membership to items.
+//!
+//! The sets are maintained in the `[[set]]` sections of `rules.toml`, see [rules](crate::rules).
+
+use std::collections::HashMap;
+
+use data::{ArmourSet, Item};
+
+use crate::rules::{Origin, Rules};
+
+/// Armour sets by item name, with the location of the rule.
+pub type Table = HashMap<String, (Vec<ArmourSet>, Origin)>;
+
+/// A copy of the sets of `rules`, which is consumed by [`apply_sets`] and [`check`].
+#[must_use]
+pub fn table(rules: &Rules) -> Table {
+    rules.armour_sets.clone()
+}
+
+/// Applies the armour sets of `item`.
+pub fn apply_sets(table: &mut Table, item: &mut Item) {
+    if let Some((sets, _)) = table.remove(&item.name) {
+        item.armour_sets = sets;
+    }
+}
+
+/// Return errors for leftover names in the set list (probably typos).
+#[must_use]
+pub fn check(table: &Table) -> Vec<String> {
+    table
+        .iter()
+        .map(|(name, (_, origin))| format!("Missed armour set: {} ({})", name, origin))
+        .collect()
+}
@@ -0,0 +1,50 @@
+//! Adds non-trading sources to items.
+//!
+//! The sources are maintained in `sources.json`, mapping item names to a list of [`Source`]s.
+
+use std::collections::HashMap;
+
+use data::{Item, Source};
+use lazy_static::lazy_static;
+
+/// Contents of `sources.json`, the item sources by item name.
+pub const DATA: &str = include_str!("../../sources.json");
+
+lazy_static! {
+    /// Map of item names to their sources.
+    static ref ITEM_SOURCES: HashMap<String, Vec<Source>> =
+        serde_json::from_str(DATA).expect("invalid sources.json");
+}
+
+/// A copy of the table, which is consumed by [`apply_sources`] and [`check`].
+#[must_use]
+pub fn table() -> HashMap<String, Vec<Source>> {
+    ITEM_SOURCES.clone()
+}
+
+/// Applies `item.sources = <sources>` for items listed in `sources.json`. The sources of other
+/// items stay unknown.
+pub fn apply_sources(table: &mut HashMap<String, Vec<Source>>, item: &mut Item) {
+    item.attainability.sources = table.remove(&item.name);
+}
+
+/// Return errors for leftover names in the source list (probably typos).
+#[must_use]
+pub fn check(table: &HashMap<String, Vec<Source>>) -> Vec<String> {
+    table
+        .keys()
+        .map(|name| format!("Missed source data: {}", name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let sources = table();
+        assert!(sources.values().all(|sources| !sources.is_empty()));
+        assert!(sources.contains_key("Rune platebody"));
+    }
+}
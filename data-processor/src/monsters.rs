@@ -0,0 +1,58 @@
+//! Filter and merge monster data.
+//!
+//! Many monsters exist in multiple variants which only differ in appearance or location. Variants
+//! with identical name and combat relevant stats are merged into the one with the lowest id.
+
+use std::collections::HashMap;
+
+use data::{Monster, MonsterDatabase};
+
+use crate::osrsbox::MonsterProperties;
+
+/// Return true if the monster should be kept.
+#[must_use]
+pub fn keep(monster: &MonsterProperties) -> bool {
+    // Remove duplicates
+    if monster.duplicate {
+        return false;
+    }
+
+    // Remove monsters which can not be fought
+    if monster.combat_level == 0 || monster.hitpoints.unwrap_or(0) == 0 {
+        return false;
+    }
+
+    monster.wiki_url.is_some()
+}
+
+/// Merge variants of the same monster with identical stats.
+#[must_use]
+pub fn merge(monsters: impl IntoIterator<Item = Monster>) -> MonsterDatabase {
+    let mut monsters: Vec<_> = monsters.into_iter().collect();
+    monsters.sort_unstable_by_key(|m| m.id);
+
+    let mut merged: Vec<Monster> = Vec::new();
+    let mut variants = HashMap::new();
+    for monster in monsters {
+        let key = (
+            monster.name.clone(),
+            monster.combat_level,
+            monster.hitpoints,
+            monster.size,
+            monster.defence_level,
+            monster.magic_level,
+            monster.defence,
+            monster.attributes.clone(),
+        );
+
+        if let Some(&idx) = variants.get(&key) {
+            let target: &mut Monster = &mut merged[idx];
+            target.source_ids.extend(monster.source_ids);
+        } else {
+            variants.insert(key, merged.len());
+            merged.push(monster);
+        }
+    }
+
+    merged.into_iter().collect()
+}
@@ -0,0 +1,71 @@
+use std::fmt::{self, Display};
+
+use super::{ItemAttackStyle, ItemAttackType, Stance};
+
+/// Errors when converting [OSRSBox](https://www.osrsbox.com/) records to the output format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The record is marked as duplicate and should have been filtered.
+    Duplicate,
+    /// A field required by the output format is missing.
+    MissingField(&'static str),
+    /// A weapon stance which does not match any known combat option.
+    UnknownStance {
+        /// The name of the stance.
+        combat_style: String,
+        /// The type of damage dealt by the attack.
+        attack_type: Option<ItemAttackType>,
+        /// The attack style.
+        attack_style: Option<ItemAttackStyle>,
+        /// The types of experience gained.
+        experience: Option<String>,
+    },
+    /// The icon is not a valid base64 encoded PNG image, or it is fully transparent.
+    BadIcon(String),
+}
+
+impl ConversionError {
+    /// Short description of the kind of error, for summaries.
+    #[must_use]
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Duplicate => "duplicate",
+            Self::MissingField(_) => "missing field",
+            Self::UnknownStance { .. } => "unknown stance",
+            Self::BadIcon(_) => "bad icon",
+        }
+    }
+
+    /// Unknown stance error with the fields of `stance`.
+    #[must_use]
+    pub fn unknown_stance(stance: Stance) -> Self {
+        Self::UnknownStance {
+            combat_style: stance.combat_style,
+            attack_type: stance.attack_type,
+            attack_style: stance.attack_style,
+            experience: stance.experience,
+        }
+    }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Duplicate => write!(f, "duplicate record"),
+            Self::MissingField(field) => write!(f, "missing {}", field),
+            Self::UnknownStance {
+                combat_style,
+                attack_type,
+                attack_style,
+                experience,
+            } => write!(
+                f,
+                "unknown stance \"{}\" (type {:?}, style {:?}, experience {:?})",
+                combat_style, attack_type, attack_style, experience
+            ),
+            Self::BadIcon(e) => write!(f, "bad icon: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
@@ -1,8 +1,8 @@
-use data::{Attainability, Item};
+use data::{Attainability, Item, Price};
 use image::{bmp::BmpEncoder, DynamicImage, GenericImageView, ImageFormat};
 use serde::Deserialize;
 
-use super::{ItemEquipment, ItemWeapon};
+use super::{ConversionError, ItemEquipment, ItemWeapon};
 
 /// [OSRSBox](https://www.osrsbox.com/) [`ItemProperties`](https://www.osrsbox.com/projects/osrsbox-db/#item-properties).
 #[allow(clippy::struct_excessive_bools)]
@@ -76,26 +76,49 @@ pub struct ItemProperties {
     pub weapon: Option<ItemWeapon>,
 }
 
-impl From<ItemProperties> for Item {
-    fn from(item: ItemProperties) -> Self {
-        assert!(!item.duplicate);
+impl TryFrom<ItemProperties> for Item {
+    type Error = ConversionError;
 
-        let equipment = item.equipment.expect("Missing equipment stats.");
+    fn try_from(item: ItemProperties) -> Result<Self, Self::Error> {
+        if item.duplicate {
+            return Err(ConversionError::Duplicate);
+        }
+
+        let equipment = item
+            .equipment
+            .ok_or(ConversionError::MissingField("equipment stats"))?;
         let equip_slot = equipment.slot;
         let requirements = equipment.requirements();
 
-        Self {
+        Ok(Self {
+            id: item.id,
+            source_ids: vec![item.id],
             name: item.name,
             members: item.members,
-            weight: item.weight.expect("Missing weight."),
-            wiki_url: item.wiki_url.expect("Missing wiki url."),
-            icon_data: trim_icon(&item.icon).unwrap(),
+            weight: item.weight.ok_or(ConversionError::MissingField("weight"))?,
+            wiki_url: item
+                .wiki_url
+                .ok_or(ConversionError::MissingField("wiki url"))?,
+            icon_data: if item.icon.is_empty() {
+                Vec::new()
+            } else {
+                trim_icon(&item.icon).map_err(ConversionError::BadIcon)?
+            },
             combat_stats: equipment.into(),
-            weapon_data: item.weapon.map(Into::into),
+            conditional_bonuses: Vec::new(),
+            armour_sets: Vec::new(),
+            weapon_data: item.weapon.map(TryInto::try_into).transpose()?,
             equip_slot,
             requirements,
             attainability: Attainability::new(item.tradeable),
-        }
+            price: Price {
+                store: item.cost,
+                high_alch: item.highalch,
+                buy_limit: item.buy_limit,
+                market: None,
+                reclaim: None,
+            },
+        })
     }
 }
 
@@ -105,7 +128,7 @@ fn trim_icon(data: &str) -> Result<Vec<u8>, String> {
     let image = image::load_from_memory_with_format(&bytes, ImageFormat::Png)
         .map_err(|e| format!("Failed to decode PNG: {}", e))?;
 
-    let (x, y, w, h) = find_dimensions(&image);
+    let (x, y, w, h) = find_dimensions(&image)?;
     let image = image.crop_imm(x, y, w, h);
 
     let mut output = Vec::new();
@@ -117,7 +140,8 @@ fn trim_icon(data: &str) -> Result<Vec<u8>, String> {
     Ok(output)
 }
 
-fn find_dimensions(image: &DynamicImage) -> (u32, u32, u32, u32) {
+/// Bounding box of the visible pixels of `image` as `(x, y, width, height)`.
+fn find_dimensions(image: &DynamicImage) -> Result<(u32, u32, u32, u32), String> {
     let (w, h) = image.dimensions();
 
     let (mut x0, mut y0) = (u32::MAX, u32::MAX);
@@ -134,5 +158,33 @@ fn find_dimensions(image: &DynamicImage) -> (u32, u32, u32, u32) {
         }
     }
 
-    (x0, y0, x1 - x0, y1 - y0)
+    if x0 > x1 {
+        return Err("Icon is fully transparent".to_string());
+    }
+    Ok((x0, y0, x1 - x0 + 1, y1 - y0 + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use image::{Rgba, RgbaImage};
+
+    use super::*;
+
+    #[test]
+    fn dimensions() {
+        let mut image = RgbaImage::new(6, 5);
+        assert!(find_dimensions(&DynamicImage::ImageRgba8(image.clone())).is_err());
+
+        image.put_pixel(1, 2, Rgba([255, 0, 0, 255]));
+        assert_eq!(
+            find_dimensions(&DynamicImage::ImageRgba8(image.clone())),
+            Ok((1, 2, 1, 1))
+        );
+
+        image.put_pixel(4, 3, Rgba([0, 0, 0, 1]));
+        assert_eq!(
+            find_dimensions(&DynamicImage::ImageRgba8(image)),
+            Ok((1, 2, 4, 2))
+        );
+    }
 }
@@ -15,7 +15,7 @@ where
         }
         fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
             (value == NAME)
-                .then(|| ())
+                .then_some(())
                 .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(value), &self))
         }
     }
@@ -1,6 +1,8 @@
 use data::{AttackStyle, CombatOption, DamageType, WeaponData};
 use serde::Deserialize;
 
+use super::ConversionError;
+
 /// [OSRSBox](https://www.osrsbox.com/) [`ItemWeapon`](https://www.osrsbox.com/projects/osrsbox-db/#item-weapon).
 #[derive(Debug, Clone, Deserialize)]
 pub struct ItemWeapon {
@@ -12,16 +14,19 @@ pub struct ItemWeapon {
     pub stances: Vec<Stance>,
 }
 
-impl From<ItemWeapon> for WeaponData {
-    fn from(weapon: ItemWeapon) -> Self {
-        Self {
-            attack_delay: weapon.attack_speed,
-            combat_options: weapon
-                .stances
-                .into_iter()
-                .filter_map(Stance::into)
-                .collect(),
+impl TryFrom<ItemWeapon> for WeaponData {
+    type Error = ConversionError;
+
+    fn try_from(weapon: ItemWeapon) -> Result<Self, Self::Error> {
+        let mut combat_options = Vec::new();
+        for stance in weapon.stances {
+            combat_options.extend(Option::<CombatOption>::try_from(stance)?);
         }
+
+        Ok(Self {
+            attack_delay: weapon.attack_speed,
+            combat_options,
+        })
     }
 }
 
@@ -41,16 +46,17 @@ pub enum ItemAttackStyle {
     Magic,
 }
 
-impl From<ItemAttackStyle> for AttackStyle {
-    fn from(style: ItemAttackStyle) -> Self {
+/// Magic is handled differently due to defensive auto-casting and fails to convert.
+impl TryFrom<ItemAttackStyle> for AttackStyle {
+    type Error = ItemAttackStyle;
+
+    fn try_from(style: ItemAttackStyle) -> Result<Self, Self::Error> {
         match style {
-            ItemAttackStyle::Accurate => Self::Accurate,
-            ItemAttackStyle::Aggressive => Self::Aggressive,
-            ItemAttackStyle::Controlled => Self::Controlled,
-            ItemAttackStyle::Defensive => Self::Defensive,
-            ItemAttackStyle::Magic => {
-                unreachable!("magic is handled differently due to defensive auto-casting")
-            }
+            ItemAttackStyle::Accurate => Ok(Self::Accurate),
+            ItemAttackStyle::Aggressive => Ok(Self::Aggressive),
+            ItemAttackStyle::Controlled => Ok(Self::Controlled),
+            ItemAttackStyle::Defensive => Ok(Self::Defensive),
+            ItemAttackStyle::Magic => Err(style),
         }
     }
 }
@@ -76,14 +82,17 @@ pub enum ItemAttackType {
     DefensiveCasting,
 }
 
-impl From<ItemAttackType> for DamageType {
-    fn from(attack_type: ItemAttackType) -> Self {
+/// Magic and casting types depend on the stance and fail to convert.
+impl TryFrom<ItemAttackType> for DamageType {
+    type Error = ItemAttackType;
+
+    fn try_from(attack_type: ItemAttackType) -> Result<Self, Self::Error> {
         match attack_type {
-            ItemAttackType::Stab => Self::Stab,
-            ItemAttackType::Slash => Self::Slash,
-            ItemAttackType::Crush => Self::Crush,
-            ItemAttackType::Ranged => Self::Ranged,
-            _ => unreachable!(),
+            ItemAttackType::Stab => Ok(Self::Stab),
+            ItemAttackType::Slash => Ok(Self::Slash),
+            ItemAttackType::Crush => Ok(Self::Crush),
+            ItemAttackType::Ranged => Ok(Self::Ranged),
+            _ => Err(attack_type),
         }
     }
 }
@@ -103,8 +112,11 @@ pub struct Stance {
     pub boosts: Option<String>,
 }
 
-impl From<Stance> for Option<CombatOption> {
-    fn from(stance: Stance) -> Self {
+/// Stances without a combat option (e.g. blocking) convert to `None`.
+impl TryFrom<Stance> for Option<CombatOption> {
+    type Error = ConversionError;
+
+    fn try_from(stance: Stance) -> Result<Self, Self::Error> {
         #[allow(clippy::match_same_arms)] // easier to organize
         let (style, damage_type) = match (
             stance.attack_type,
@@ -121,18 +133,29 @@ impl From<Stance> for Option<CombatOption> {
             }
             // Melee
             (Some(attack_type), Some(attack_style), _, _) => {
-                (attack_style.into(), attack_type.into())
+                match (attack_style.try_into(), attack_type.try_into()) {
+                    (Ok(style), Ok(damage_type)) => (style, damage_type),
+                    _ => return Err(ConversionError::unknown_stance(stance)),
+                }
             }
             // [Salamander](https://oldschool.runescape.wiki/w/Salamander)- cases
-            (Some(ItemAttackType::Slash), _, "scorch", Some("strength")) => (AttackStyle::Aggressive, DamageType::Slash),
-            (Some(ItemAttackType::Ranged), _, "flare", Some("ranged")) => (AttackStyle::Accurate, DamageType::Ranged),
-            (Some(ItemAttackType::Magic), _, "blaze", Some("magic")) => (AttackStyle::Defensive, DamageType::Magic),
+            (Some(ItemAttackType::Slash), _, "scorch", Some("strength")) => {
+                (AttackStyle::Aggressive, DamageType::Slash)
+            }
+            (Some(ItemAttackType::Ranged), _, "flare", Some("ranged")) => {
+                (AttackStyle::Accurate, DamageType::Ranged)
+            }
+            (Some(ItemAttackType::Magic), _, "blaze", Some("magic")) => {
+                (AttackStyle::Defensive, DamageType::Magic)
+            }
             // [Chinchompa](https://oldschool.runescape.wiki/w/Chinchompa_(weapon))
             (_, _, "short fuse", Some("ranged")) => (AttackStyle::Accurate, DamageType::Ranged),
             (_, _, "medium fuse", Some("ranged")) => (AttackStyle::Rapid, DamageType::Ranged),
-            (_, _, "long fuse", Some("ranged and defence")) => (AttackStyle::LongRange, DamageType::Ranged),
+            (_, _, "long fuse", Some("ranged and defence")) => {
+                (AttackStyle::LongRange, DamageType::Ranged)
+            }
             // [Dinh's bulwark](https://oldschool.runescape.wiki/w/Dinh%27s_bulwark)
-            (None, None, "block", None) => return None,
+            (None, None, "block", None) => return Ok(None),
             // Ranged weapons
             (_, _, "accurate", Some("ranged")) => (AttackStyle::Accurate, DamageType::Ranged),
             (_, _, "rapid", Some("ranged")) => (AttackStyle::Rapid, DamageType::Ranged),
@@ -144,25 +167,19 @@ impl From<Stance> for Option<CombatOption> {
             (_, _, "longrange", Some("magic and defence")) => {
                 (AttackStyle::LongRange, DamageType::Magic)
             }
-            _ => unreachable!(
-                "Unknown combat option: type \"{:?}\", style \"{:?}\", name \"{}\", experience \"{:?}\".",
-                stance.attack_type, stance.attack_style, stance.combat_style, stance.experience
-            ),
+            _ => return Err(ConversionError::unknown_stance(stance)),
         };
 
-        let name = stance.combat_style;
-        let name = name
-            .chars()
-            .next()
-            .unwrap()
-            .to_uppercase()
-            .chain(name.chars().skip(1))
-            .collect();
+        let mut chars = stance.combat_style.chars();
+        let Some(first) = chars.next() else {
+            return Err(ConversionError::unknown_stance(stance));
+        };
+        let name = first.to_uppercase().chain(chars).collect();
 
-        Some(CombatOption {
+        Ok(Some(CombatOption {
             name,
             style,
             damage_type,
-        })
+        }))
     }
 }
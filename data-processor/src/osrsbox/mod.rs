@@ -1,27 +1,45 @@
 //! Data format used by the [OSRSBox](https://www.osrsbox.com/) API.
 
+pub use conversion_error::*;
 pub use item_equipment::*;
 pub use item_properties::*;
 pub use item_requirement::*;
 pub use item_weapon::*;
+pub use monster_properties::*;
 
+mod conversion_error;
 mod item_equipment;
 mod item_properties;
 mod item_requirement;
 mod item_weapon;
+mod monster_properties;
 
+/// OSRSBox JSON of the Helm of neitiznot, shared by tests.
 #[cfg(test)]
+pub(crate) const HELM_OF_NEITIZNOT: &str = r#"{"id": 10828, "name": "Helm of neitiznot", "last_updated": "2021-08-05", "incomplete": false, "members": true, "tradeable": true, "tradeable_on_ge": true, "stackable": false, "stacked": null, "noted": false, "noteable": true, "linked_id_item": null, "linked_id_noted": 10843, "linked_id_placeholder": 17668, "placeholder": false, "equipable": true, "equipable_by_player": true, "equipable_weapon": false, "cost": 50000, "lowalch": 20000, "highalch": 30000, "weight": 2.267, "buy_limit": 70, "quest_item": false, "release_date": "2007-02-06", "duplicate": false, "examine": "A gift from Neitiznot's Burgher.", "icon": "iVBORw0KGgoAAAANSUhEUgAAACQAAAAgCAYAAAB6kdqOAAACyElEQVR4XtWW70tTURzG/f4JvghEEMYQkbEXIxziaDgUh4aKhflCLeb6IXO5tDWtpRnq1PzBpm2L/NVaSkkjUQQtpfBVRC+iv+jJcy67zOt058TuogcujJ0v5/mc55z7Pbeo6P8WQfvPxZKtlxIhHApB3IQQn5+TqJcWYXtjgxtYDAYBE8K7V3GJeikRYrMv1MlzGyhpbieTegAR1paW1O0Sm5ywEolILEBKhPnxNonJCcvhMD6srkgsQFiEZKybb1d6xdqKs1K2d2FiQh+g6dFmDPXXI5VICBgQUus9mB1rlUhUSoTxx03oc7sFDQivFzoQmZoSgP8rEUb8TrycmRE0IISCV3miYvVSUuIf8HjwJhoVNCCMBRoF05QWYX2xEw0OB7bW14SBngw0CNZK66S5TV6D3+vVrJi9adpHqd9adaGns1M/oMz40+Zs+9iZmgwGOezt7i51LDrTroHPq5QDmjZj9xhrdm9jsVNALJFyo5HXnF5A3kVqPznc2cF+KoWdzU28X77FG19o5CkC/fd5Qs1OJy4VF+t5fpgIi1PX8XVvD0e7uxzq80cPPyfss2L62SiGH/hUILPJhEfeOj2BmAjHB/scigEdpvrwKeE+k1BrUyOsFgt8d+3/BigzoV6XC+0tLai12U5+2/QH+vUtgJ+Hfvz48lAFSkS7+Haya2WwtxY3O6xorKvE0L1q/YF+Hw9zoO8HgyoMa5jxuRvqxXunuwZtTWY89xUgoZoqMyzmChgNpRyIJcP+z3xanHaUlpTwN013oCvVFg5UUW5QYdKNL90w6+1WDpQ5pp0pT1KArBYTB8rsxJk1hrKyQqTDRHDYLnMgc6WRf+ucNaVCbReTAsTOEQPKviVKQtnH8i7lfDCg7IbEOzR7CpAOkwLEzlF2Q/YWVp0Dq4sITkfNBYaEOrvu14VW58GklWtcB+UyyzUuoz9ZOF1UKKk1OwAAAABJRU5ErkJggg==", "wiki_name": "Helm of neitiznot", "wiki_url": "https://oldschool.runescape.wiki/w/Helm_of_neitiznot", "wiki_exchange": "https://oldschool.runescape.wiki/w/Exchange:Helm_of_neitiznot", "equipment": {"attack_stab": 0, "attack_slash": 0, "attack_crush": 0, "attack_magic": 0, "attack_ranged": 0, "defence_stab": 31, "defence_slash": 29, "defence_crush": 34, "defence_magic": 3, "defence_ranged": 30, "melee_strength": 3, "ranged_strength": 0, "magic_damage": 0, "prayer": 3, "slot": "head", "requirements": {"defence": 55}}, "weapon": null}"#;
+
+/// Copy of the [`HELM_OF_NEITIZNOT`] with another id, name and prayer bonus, shared by tests.
+#[cfg(test)]
+pub(crate) fn helm(id: u32, name: &str, prayer: i64) -> ItemProperties {
+    let mut value: serde_json::Value = serde_json::from_str(HELM_OF_NEITIZNOT).unwrap();
+    value["id"] = serde_json::json!(id);
+    value["name"] = serde_json::json!(name);
+    value["wiki_name"] = serde_json::json!(name);
+    value["equipment"]["prayer"] = serde_json::json!(prayer);
+    serde_json::from_value(value).unwrap()
+}
+
+#[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod tests {
-    use data::{DamageType, EquipSlot, Item, RequirementType, Skill};
+    use data::{DamageType, EquipSlot, Item, Monster, MonsterAttribute, RequirementType, Skill};
     use enum_iterator::IntoEnumIterator;
 
     use super::*;
 
     #[test]
     fn test_equipment() {
-        let i: ItemProperties = serde_json::from_str(
-            r#"{"id": 10828, "name": "Helm of neitiznot", "last_updated": "2021-08-05", "incomplete": false, "members": true, "tradeable": true, "tradeable_on_ge": true, "stackable": false, "stacked": null, "noted": false, "noteable": true, "linked_id_item": null, "linked_id_noted": 10843, "linked_id_placeholder": 17668, "placeholder": false, "equipable": true, "equipable_by_player": true, "equipable_weapon": false, "cost": 50000, "lowalch": 20000, "highalch": 30000, "weight": 2.267, "buy_limit": 70, "quest_item": false, "release_date": "2007-02-06", "duplicate": false, "examine": "A gift from Neitiznot's Burgher.", "icon": "iVBORw0KGgoAAAANSUhEUgAAACQAAAAgCAYAAAB6kdqOAAACyElEQVR4XtWW70tTURzG/f4JvghEEMYQkbEXIxziaDgUh4aKhflCLeb6IXO5tDWtpRnq1PzBpm2L/NVaSkkjUQQtpfBVRC+iv+jJcy67zOt058TuogcujJ0v5/mc55z7Pbeo6P8WQfvPxZKtlxIhHApB3IQQn5+TqJcWYXtjgxtYDAYBE8K7V3GJeikRYrMv1MlzGyhpbieTegAR1paW1O0Sm5ywEolILEBKhPnxNonJCcvhMD6srkgsQFiEZKybb1d6xdqKs1K2d2FiQh+g6dFmDPXXI5VICBgQUus9mB1rlUhUSoTxx03oc7sFDQivFzoQmZoSgP8rEUb8TrycmRE0IISCV3miYvVSUuIf8HjwJhoVNCCMBRoF05QWYX2xEw0OB7bW14SBngw0CNZK66S5TV6D3+vVrJi9adpHqd9adaGns1M/oMz40+Zs+9iZmgwGOezt7i51LDrTroHPq5QDmjZj9xhrdm9jsVNALJFyo5HXnF5A3kVqPznc2cF+KoWdzU28X77FG19o5CkC/fd5Qs1OJy4VF+t5fpgIi1PX8XVvD0e7uxzq80cPPyfss2L62SiGH/hUILPJhEfeOj2BmAjHB/scigEdpvrwKeE+k1BrUyOsFgt8d+3/BigzoV6XC+0tLai12U5+2/QH+vUtgJ+Hfvz48lAFSkS7+Haya2WwtxY3O6xorKvE0L1q/YF+Hw9zoO8HgyoMa5jxuRvqxXunuwZtTWY89xUgoZoqMyzmChgNpRyIJcP+z3xanHaUlpTwN013oCvVFg5UUW5QYdKNL90w6+1WDpQ5pp0pT1KArBYTB8rsxJk1hrKyQqTDRHDYLnMgc6WRf+ucNaVCbReTAsTOEQPKviVKQtnH8i7lfDCg7IbEOzR7CpAOkwLEzlF2Q/YWVp0Dq4sITkfNBYaEOrvu14VW58GklWtcB+UyyzUuoz9ZOF1UKKk1OwAAAABJRU5ErkJggg==", "wiki_name": "Helm of neitiznot", "wiki_url": "https://oldschool.runescape.wiki/w/Helm_of_neitiznot", "wiki_exchange": "https://oldschool.runescape.wiki/w/Exchange:Helm_of_neitiznot", "equipment": {"attack_stab": 0, "attack_slash": 0, "attack_crush": 0, "attack_magic": 0, "attack_ranged": 0, "defence_stab": 31, "defence_slash": 29, "defence_crush": 34, "defence_magic": 3, "defence_ranged": 30, "melee_strength": 3, "ranged_strength": 0, "magic_damage": 0, "prayer": 3, "slot": "head", "requirements": {"defence": 55}}, "weapon": null}"#,
-        ).unwrap();
+        let i: ItemProperties = serde_json::from_str(HELM_OF_NEITIZNOT).unwrap();
 
         assert_eq!(i.id, 10828);
         assert_eq!(i.name, "Helm of neitiznot");
@@ -95,8 +113,10 @@ mod tests {
         assert!(i.weapon.is_none());
 
         // Projection
-        let i = Item::from(i);
+        let i = Item::try_from(i).unwrap();
 
+        assert_eq!(i.id, 10828);
+        assert_eq!(i.source_ids, [10828]);
         assert_eq!(i.name, "Helm of neitiznot");
         assert_eq!(i.members, true);
         assert_eq!(i.weight, 2.267);
@@ -228,8 +248,10 @@ mod tests {
         assert!(s.boosts.is_none());
 
         // Projection
-        let i = Item::from(i);
+        let i = Item::try_from(i).unwrap();
 
+        assert_eq!(i.id, 4151);
+        assert_eq!(i.source_ids, [4151]);
         assert_eq!(i.name, "Abyssal whip");
         assert_eq!(i.members, true);
         assert_eq!(i.weight, 0.453);
@@ -259,4 +281,76 @@ mod tests {
 
         assert_eq!(i.weapon_data.unwrap().attack_delay, 4);
     }
+
+    #[test]
+    fn test_monster() {
+        let m: MonsterProperties = serde_json::from_str(
+            r#"{"id": 415, "name": "Abyssal demon", "last_updated": "2021-08-05", "incomplete": false, "members": true, "release_date": "2005-01-26", "combat_level": 124, "size": 1, "hitpoints": 150, "max_hit": 8, "attack_type": ["stab"], "attack_speed": 4, "aggressive": false, "poisonous": false, "venomous": false, "immune_poison": false, "immune_venom": false, "attributes": ["demon"], "category": ["abyssal demon"], "slayer_monster": true, "slayer_level": 85, "slayer_xp": 150.0, "slayer_masters": ["vannaka", "chaeldar", "konar", "nieve", "duradel"], "duplicate": false, "examine": "A denizen of the Abyss!", "wiki_name": "Abyssal demon (Standard)", "wiki_url": "https://oldschool.runescape.wiki/w/Abyssal_demon#Standard", "attack_level": 97, "strength_level": 67, "defence_level": 135, "magic_level": 1, "ranged_level": 1, "attack_bonus": 0, "strength_bonus": 0, "attack_magic": 0, "magic_bonus": 0, "attack_ranged": 0, "ranged_bonus": 0, "defence_stab": 20, "defence_slash": 20, "defence_crush": 20, "defence_magic": 0, "defence_ranged": 20, "drops": []}"#,
+        )
+        .unwrap();
+
+        assert_eq!(m.id, 415);
+        assert_eq!(m.name, "Abyssal demon");
+        assert_eq!(m.combat_level, 124);
+        assert_eq!(m.hitpoints, Some(150));
+        assert_eq!(m.attack_speed, Some(4));
+        assert_eq!(m.attributes, ["demon"]);
+        assert_eq!(m.slayer_level, Some(85));
+        assert_eq!(m.defence_level, 135);
+        assert_eq!(m.defence_slash, 20);
+        assert_eq!(m.defence_magic, 0);
+
+        // Projection
+        let m = Monster::try_from(m).unwrap();
+
+        assert_eq!(m.id, 415);
+        assert_eq!(m.source_ids, [415]);
+        assert_eq!(m.name, "Abyssal demon");
+        assert_eq!(m.hitpoints, 150);
+        assert_eq!(m.size, 1);
+        assert_eq!(m.defence_level, 135);
+        assert_eq!(m.magic_level, 1);
+        assert_eq!(m.attributes, [MonsterAttribute::Demon]);
+        assert!(m.slayer_monster);
+
+        for (damage_type, def) in DamageType::into_enum_iter().zip([20, 20, 20, 0, 20]) {
+            assert_eq!(m.defence[damage_type], def);
+        }
+    }
+
+    #[test]
+    fn conversion_errors() {
+        let item = |field: &str, value: serde_json::Value| {
+            let mut json: serde_json::Value = serde_json::from_str(HELM_OF_NEITIZNOT).unwrap();
+            json[field] = value;
+            let item: ItemProperties = serde_json::from_value(json).unwrap();
+            Item::try_from(item)
+        };
+
+        assert!(item("name", "Helm".into()).is_ok());
+        assert_eq!(
+            item("weight", serde_json::Value::Null).unwrap_err(),
+            ConversionError::MissingField("weight")
+        );
+        assert_eq!(
+            item("duplicate", true.into()).unwrap_err(),
+            ConversionError::Duplicate
+        );
+        let error = item("icon", "AAAA".into()).unwrap_err();
+        assert_eq!(error.kind(), "bad icon");
+
+        let stance = Stance {
+            combat_style: "bash".to_string(),
+            attack_type: Some(ItemAttackType::Magic),
+            attack_style: Some(ItemAttackStyle::Aggressive),
+            experience: None,
+            boosts: None,
+        };
+        let error = Option::<data::CombatOption>::try_from(stance).unwrap_err();
+        assert_eq!(error.kind(), "unknown stance");
+        assert_eq!(
+            error.to_string(),
+            "unknown stance \"bash\" (type Some(Magic), style Some(Aggressive), experience None)"
+        );
+    }
 }
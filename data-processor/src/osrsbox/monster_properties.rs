@@ -0,0 +1,167 @@
+use data::{DamageTypeStats, Monster, MonsterAttribute};
+use serde::Deserialize;
+
+use super::ConversionError;
+
+/// [OSRSBox](https://www.osrsbox.com/) [`MonsterProperties`](https://www.osrsbox.com/projects/osrsbox-db/#monster-properties).
+///
+/// The `drops` property is not used and therefore not parsed.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Deserialize)]
+pub struct MonsterProperties {
+    /// Unique OSRS monster ID number.
+    pub id: u32,
+    /// The name of the monster.
+    pub name: String,
+    /// The last time (UTC) the monster was updated (in ISO8601 date format).
+    pub last_updated: Option<String>,
+    /// If the monster has incomplete wiki data.
+    pub incomplete: bool,
+    /// If the monster is members only, or not.
+    pub members: bool,
+    /// The release date of the monster (in ISO8601 date format).
+    pub release_date: Option<String>,
+    /// The combat level of the monster.
+    pub combat_level: u16,
+    /// The size, in tiles, of the monster.
+    pub size: u8,
+    /// The number of hitpoints a monster has.
+    pub hitpoints: Option<u16>,
+    /// The maximum hit of the monster.
+    pub max_hit: Option<u16>,
+    /// The attack style (e.g., melee, magic, range) of the monster.
+    pub attack_type: Vec<String>,
+    /// The attack speed (in game ticks) of the monster.
+    pub attack_speed: Option<u8>,
+    /// If the monster is aggressive, or not.
+    pub aggressive: bool,
+    /// If the monster poisons, or not.
+    pub poisonous: bool,
+    /// If the monster uses venom, or not.
+    pub venomous: bool,
+    /// If the monster is immune to poison, or not.
+    pub immune_poison: bool,
+    /// If the monster is immune to venom, or not.
+    pub immune_venom: bool,
+    /// An array of monster attributes.
+    pub attributes: Vec<String>,
+    /// An array of monster category.
+    pub category: Vec<String>,
+    /// If the monster is a potential slayer task.
+    pub slayer_monster: bool,
+    /// The slayer level required to kill the monster.
+    pub slayer_level: Option<u8>,
+    /// The slayer XP rewarded for a monster kill.
+    pub slayer_xp: Option<f32>,
+    /// The slayer masters who can assign the monster.
+    pub slayer_masters: Vec<String>,
+    /// If the monster is a duplicate.
+    pub duplicate: bool,
+    /// The examine text of the monster.
+    pub examine: Option<String>,
+    /// The OSRS Wiki name for the monster.
+    pub wiki_name: Option<String>,
+    /// The OSRS Wiki URL (possibly including anchor link).
+    pub wiki_url: Option<String>,
+    /// The attack level of the monster.
+    pub attack_level: u16,
+    /// The strength level of the monster.
+    pub strength_level: u16,
+    /// The defence level of the monster.
+    pub defence_level: u16,
+    /// The magic level of the monster.
+    pub magic_level: u16,
+    /// The ranged level of the monster.
+    pub ranged_level: u16,
+    /// The attack bonus of the monster.
+    pub attack_bonus: i16,
+    /// The strength bonus of the monster.
+    pub strength_bonus: i16,
+    /// The magic attack of the monster.
+    pub attack_magic: i16,
+    /// The magic bonus of the monster.
+    pub magic_bonus: i16,
+    /// The ranged attack of the monster.
+    pub attack_ranged: i16,
+    /// The ranged bonus of the monster.
+    pub ranged_bonus: i16,
+    /// The defence stab bonus of the monster.
+    pub defence_stab: i16,
+    /// The defence slash bonus of the monster.
+    pub defence_slash: i16,
+    /// The defence crush bonus of the monster.
+    pub defence_crush: i16,
+    /// The defence magic bonus of the monster.
+    pub defence_magic: i16,
+    /// The defence ranged bonus of the monster.
+    pub defence_ranged: i16,
+}
+
+impl MonsterProperties {
+    /// Defence bonuses, ordered by [`DamageType`](data::DamageType).
+    #[must_use]
+    pub fn defence(&self) -> DamageTypeStats {
+        DamageTypeStats::new(&[
+            self.defence_stab,
+            self.defence_slash,
+            self.defence_crush,
+            self.defence_magic,
+            self.defence_ranged,
+        ])
+    }
+}
+
+impl TryFrom<MonsterProperties> for Monster {
+    type Error = ConversionError;
+
+    fn try_from(monster: MonsterProperties) -> Result<Self, Self::Error> {
+        if monster.duplicate {
+            return Err(ConversionError::Duplicate);
+        }
+
+        let defence = monster.defence();
+        Ok(Self {
+            id: monster.id,
+            source_ids: vec![monster.id],
+            name: monster.name,
+            wiki_url: monster
+                .wiki_url
+                .ok_or(ConversionError::MissingField("wiki url"))?,
+            members: monster.members,
+            combat_level: monster.combat_level,
+            hitpoints: monster
+                .hitpoints
+                .ok_or(ConversionError::MissingField("hitpoints"))?,
+            size: monster.size,
+            defence_level: monster.defence_level,
+            magic_level: monster.magic_level,
+            defence,
+            // Attributes without equipment effects are dropped
+            attributes: monster
+                .attributes
+                .iter()
+                .filter_map(|a| attribute(a))
+                .collect(),
+            slayer_monster: monster.slayer_monster,
+        })
+    }
+}
+
+fn attribute(name: &str) -> Option<MonsterAttribute> {
+    Some(match name {
+        "demon" => MonsterAttribute::Demon,
+        "dragon" => MonsterAttribute::Dragon,
+        "fiery" => MonsterAttribute::Fiery,
+        "golem" => MonsterAttribute::Golem,
+        "kalphite" => MonsterAttribute::Kalphite,
+        "leafy" => MonsterAttribute::Leafy,
+        "penance" => MonsterAttribute::Penance,
+        "rat" => MonsterAttribute::Rat,
+        "shade" => MonsterAttribute::Shade,
+        "spectral" => MonsterAttribute::Spectral,
+        "undead" => MonsterAttribute::Undead,
+        "vampyre" => MonsterAttribute::Vampyre,
+        "xerician" => MonsterAttribute::Xerician,
+        _ => return None,
+    })
+}
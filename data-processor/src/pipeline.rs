@@ -0,0 +1,227 @@
+//! The item pipeline: filter, aggregate and convert OSRSBox items.
+
+use data::Database;
+
+use crate::aggregate::Aggregator;
+use crate::explain::{Fate, Report, Trace};
+use crate::filter::Filter;
+use crate::map::Mapper;
+use crate::osrsbox::ItemProperties;
+use crate::prices::Prices;
+use crate::rules::Rules;
+use crate::source::{self, ItemSource};
+
+/// Processes OSRSBox items into a [`Database`].
+///
+/// The pipeline owns the filter, aggregation and mapping tables. Each call of
+/// [`process`](Self::process) works on a fresh copy, so a pipeline can be run repeatedly.
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    filter: Filter,
+    aggregator: Aggregator,
+    mapper: Mapper,
+    prices: Option<Prices>,
+}
+
+impl Pipeline {
+    /// Create a pipeline for the filters and aggregations of `rules`.
+    #[must_use]
+    pub fn new(rules: &Rules) -> Self {
+        Self {
+            filter: Filter::new(rules),
+            aggregator: Aggregator::new(rules),
+            mapper: Mapper::new(rules),
+            prices: None,
+        }
+    }
+
+    /// Apply the Grand Exchange `prices` to the items.
+    #[must_use]
+    pub fn with_prices(mut self, prices: Prices) -> Self {
+        self.prices = Some(prices);
+        self
+    }
+
+    /// Filter, aggregate and convert `items`.
+    ///
+    /// The report contains the fate of every item and all errors, including leftover names in the
+    /// filter, aggregation and mapping tables.
+    pub fn process(&self, items: impl IntoIterator<Item = ItemProperties>) -> (Database, Report) {
+        let mut filter = self.filter.clone();
+        let mut aggregator = self.aggregator.clone();
+        let mut mapper = self.mapper.clone();
+        let mut report = Report::default();
+
+        // All items have to be filtered before converting, so ids of filtered items can be folded
+        // into their aggregates.
+        let items: Vec<_> = items
+            .into_iter()
+            .filter(|i| match filter.removal(i, &mut aggregator) {
+                Some(fate) => {
+                    report.push(Trace::new(i, fate));
+                    false
+                }
+                None => true,
+            })
+            .collect();
+
+        let mut errors = Vec::new();
+        let database = items
+            .into_iter()
+            .filter_map(|mut i| {
+                let name = i.wiki_name.clone().unwrap_or_else(|| i.name.clone());
+                let (id, source_name, wiki_name) = (i.id, i.name.clone(), i.wiki_name.clone());
+
+                let folded = aggregator.apply(&mut i);
+                let result = mapper.map(i).map(|mut item| {
+                    item.source_ids.extend(folded);
+                    item
+                });
+
+                let fate = match &result {
+                    Ok(item) if item.source_ids.len() > 1 || item.name != source_name => {
+                        Fate::Aggregated {
+                            output_name: item.name.clone(),
+                            folded: item.source_ids[1..].to_vec(),
+                        }
+                    }
+                    Ok(item) => Fate::Kept {
+                        output_name: item.name.clone(),
+                    },
+                    Err(e) => Fate::Error {
+                        kind: e.kind().to_string(),
+                        message: e.to_string(),
+                    },
+                };
+                report.push(Trace {
+                    id,
+                    name: source_name,
+                    wiki_name,
+                    fate,
+                });
+
+                let mut item = result
+                    .map_err(|e| errors.push(format!("{}: {}", name, e)))
+                    .ok()?;
+                if let Some(prices) = &self.prices {
+                    prices.apply(&mut item);
+                }
+                Some(item)
+            })
+            .collect();
+
+        report.extend_errors(errors);
+        report.extend_errors(filter.check());
+        report.extend_errors(aggregator.check());
+        report.extend_errors(mapper.check());
+
+        (database, report)
+    }
+
+    /// Merge the items of `sources` (see [`source::merge`]) and [`process`](Self::process) them.
+    /// Conflicts and invalid merged items are added to the report.
+    ///
+    /// # Errors
+    ///
+    /// If a source can not be read.
+    pub fn process_sources(
+        &self,
+        sources: &[&dyn ItemSource],
+    ) -> Result<(Database, Report), String> {
+        let merged = source::merge(sources)?;
+        let (database, mut report) = self.process(merged.items);
+        report.extend_conflicts(merged.conflicts);
+        report.extend_errors(merged.errors);
+        Ok((database, report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osrsbox::helm;
+
+    const RULES: &str = r#"
+[filter]
+names = ["Filtered helm"]
+
+[[aggregate]]
+target = "Saradomin helm"
+name = "God helm"
+wiki_url = "https://oldschool.runescape.wiki/w/God_helm"
+fold = ["Zamorak helm"]
+"#;
+
+    fn item(id: u32, name: &str) -> ItemProperties {
+        helm(id, name, 3)
+    }
+
+    fn items() -> Vec<ItemProperties> {
+        vec![
+            item(1, "Saradomin helm"),
+            item(2, "Zamorak helm"),
+            item(3, "Filtered helm"),
+            item(4, "Plain helm"),
+        ]
+    }
+
+    #[test]
+    fn process() {
+        let pipeline = Pipeline::new(&Rules::parse(RULES, "rules.toml", false).unwrap());
+
+        let (database, report) = pipeline.process(items());
+        assert_eq!(database.len(), 2);
+        let god_helm = database.get_by_name("God helm").unwrap();
+        assert_eq!(god_helm.source_ids, [1, 2]);
+        assert!(database.get_by_name("Plain helm").is_some());
+
+        let fates: Vec<_> = (1..=4)
+            .map(|id| report.iter().find(|t| t.id == id).unwrap().fate.kind())
+            .collect();
+        assert_eq!(fates, ["aggregated", "folded", "name_filter", "kept"]);
+        assert!(!report.errors().iter().any(|e| [
+            "Saradomin helm",
+            "Zamorak helm",
+            "Filtered helm"
+        ]
+        .iter()
+        .any(|name| e.contains(name))));
+
+        // Tables are not consumed by a run
+        let (again, _) = pipeline.process(items());
+        assert_eq!(again.len(), 2);
+
+        let (_, report) = pipeline.process(vec![item(4, "Plain helm")]);
+        assert!(report
+            .errors()
+            .iter()
+            .any(|e| e == "Missed name filter: Filtered helm (rules.toml:3)"));
+    }
+
+    #[test]
+    fn reclaim_costs() {
+        let pipeline = Pipeline::new(&Rules::parse(RULES, "rules.toml", false).unwrap());
+        let untradeable = |id, name| {
+            let mut item = item(id, name);
+            item.tradeable = false;
+            item
+        };
+
+        let (database, report) = pipeline.process(vec![
+            untradeable(1, "Fire cape"),
+            untradeable(2, "Unknown cape"),
+            item(3, "Plain helm"),
+        ]);
+        let fire_cape = database.get_by_name("Fire cape").unwrap();
+        assert_eq!(fire_cape.price.reclaim, Some(50_000));
+        let missing: Vec<_> = report
+            .errors()
+            .iter()
+            .filter(|e| e.starts_with("Missing reclaim cost"))
+            .collect();
+        assert_eq!(
+            missing,
+            ["Missing reclaim cost of untradeable item: Unknown cape"]
+        );
+    }
+}
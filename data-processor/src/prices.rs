@@ -0,0 +1,67 @@
+//! Merge Grand Exchange prices into the item data.
+//!
+//! Prices are read from a local copy of the
+//! [OSRS Wiki real-time prices API](https://prices.runescape.wiki/api/v1/osrs/latest). The copy is
+//! optional, items have no market price without it.
+
+use std::collections::HashMap;
+
+use data::Item;
+use serde::Deserialize;
+
+/// Latest prices of a single item, as returned by the prices API.
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct LatestPrice {
+    /// Latest instant-buy price.
+    pub high: Option<u32>,
+    /// Latest instant-sell price.
+    pub low: Option<u32>,
+}
+
+impl LatestPrice {
+    /// Average of the buy and sell price, or the one which is available.
+    #[must_use]
+    pub fn price(self) -> Option<u32> {
+        match (self.high, self.low) {
+            (Some(high), Some(low)) => Some(u32::midpoint(high, low)),
+            (high, low) => high.or(low),
+        }
+    }
+}
+
+/// Response of the `latest` endpoint of the prices API.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Prices {
+    /// Prices by item id.
+    data: HashMap<u32, LatestPrice>,
+}
+
+impl Prices {
+    /// Set the market price of `item` to the lowest price of all ids it represents.
+    pub fn apply(&self, item: &mut Item) {
+        item.price.market = item
+            .source_ids
+            .iter()
+            .filter_map(|id| self.data.get(id).and_then(|p| p.price()))
+            .min();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let prices: Prices = serde_json::from_str(
+            r#"{"data": {
+                "4151": {"high": 1500000, "highTime": 1, "low": 1400000, "lowTime": 1},
+                "4587": {"high": 60000, "highTime": 1, "low": null, "lowTime": null}
+            }}"#,
+        )
+        .unwrap();
+
+        assert_eq!(prices.data[&4151].price(), Some(1_450_000));
+        assert_eq!(prices.data[&4587].price(), Some(60_000));
+    }
+}
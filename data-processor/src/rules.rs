@@ -0,0 +1,389 @@
+//! Declarative filter, aggregation and armour set rules, loaded from `rules.toml` at startup.
+//!
+//! Item names in the rules file are templates: `{list}` expands to every entry of the named list
+//! in the `[lists]` table, `{a..b}` expands to the integers from `a` to `b` (inclusive). Templates
+//! with several placeholders expand to all combinations.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{self, Display},
+    fs,
+    path::Path,
+    sync::Arc,
+};
+
+use data::ArmourSet;
+use serde::Deserialize;
+use toml::Spanned;
+
+/// Path of the rules file in the source tree, the default of `--rules`.
+pub const RULES_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/rules.toml");
+
+/// Location of the template an entry was expanded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origin {
+    /// Name of the rules file.
+    pub file: Arc<str>,
+    /// Line of the template, starting at `1`.
+    pub line: usize,
+}
+
+impl Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)
+    }
+}
+
+/// Replacement of an item by a stand-in item.
+#[derive(Debug, Clone)]
+pub struct Aggregation {
+    /// Name of the stand-in item.
+    pub name: String,
+    /// Wiki URL of the stand-in item.
+    pub wiki_url: String,
+    /// Location of the rule.
+    pub origin: Origin,
+}
+
+/// The expanded rules.
+#[derive(Debug, Clone, Default)]
+pub struct Rules {
+    /// Item names to remove.
+    pub names: HashMap<String, Origin>,
+    /// Wiki names of items to remove.
+    pub wiki_names: HashMap<String, Origin>,
+    /// Suffixes (after the last space) of item names to remove.
+    pub suffixes: HashSet<String>,
+    /// Suffixes (after the last ` (`) of wiki names to remove.
+    pub wiki_suffixes: HashSet<String>,
+    /// Wiki names of items to remove in favor of an aggregated item, mapped to the wiki name of
+    /// the aggregation target.
+    pub folds: HashMap<String, (String, Origin)>,
+    /// Aggregations by the wiki name of the item they replace.
+    pub aggregations: HashMap<String, Aggregation>,
+    /// Armour sets by the name of the items which are pieces of them.
+    pub armour_sets: HashMap<String, (Vec<ArmourSet>, Origin)>,
+}
+
+type Templates = Vec<Spanned<String>>;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    #[serde(default)]
+    lists: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    filter: FilterSection,
+    #[serde(default)]
+    aggregate: Vec<AggregateSection>,
+    #[serde(default)]
+    set: Vec<SetSection>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FilterSection {
+    #[serde(default)]
+    names: Templates,
+    #[serde(default)]
+    wiki_names: Templates,
+    #[serde(default)]
+    suffixes: Templates,
+    #[serde(default)]
+    wiki_suffixes: Templates,
+    #[serde(default)]
+    unless_trailblazer: Option<Box<FilterSection>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AggregateSection {
+    target: Spanned<String>,
+    name: String,
+    wiki_url: String,
+    #[serde(default)]
+    fold: Templates,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SetSection {
+    sets: Vec<ArmourSet>,
+    items: Templates,
+}
+
+/// Expands templates and records their origin.
+struct Expander<'a> {
+    file: Arc<str>,
+    source: &'a str,
+    lists: &'a HashMap<String, Vec<String>>,
+}
+
+impl Expander<'_> {
+    fn origin(&self, template: &Spanned<String>) -> Origin {
+        Origin {
+            file: self.file.clone(),
+            line: self.source[..template.start()].matches('\n').count() + 1,
+        }
+    }
+
+    /// Expand `template`, returning all names with the origin of the template.
+    fn expand(&self, template: &Spanned<String>) -> Result<Vec<(String, Origin)>, String> {
+        let origin = self.origin(template);
+        let names =
+            expand(template.get_ref(), self.lists).map_err(|e| format!("{}: {}", origin, e))?;
+        Ok(names.into_iter().map(|n| (n, origin.clone())).collect())
+    }
+
+    /// Expand all `templates` into `map`. Names produced by multiple templates are an error.
+    fn expand_into(
+        &self,
+        templates: &[Spanned<String>],
+        map: &mut HashMap<String, Origin>,
+    ) -> Result<(), String> {
+        for template in templates {
+            for (name, origin) in self.expand(template)? {
+                if let Some(previous) = map.get(&name) {
+                    return Err(format!(
+                        "{}: duplicate entry \"{}\" (also at {})",
+                        origin, name, previous
+                    ));
+                }
+                map.insert(name, origin);
+            }
+        }
+        Ok(())
+    }
+
+    fn filter(&self, section: &FilterSection, rules: &mut Rules) -> Result<(), String> {
+        let mut suffixes = HashMap::new();
+        let mut wiki_suffixes = HashMap::new();
+
+        self.expand_into(&section.names, &mut rules.names)?;
+        self.expand_into(&section.wiki_names, &mut rules.wiki_names)?;
+        self.expand_into(&section.suffixes, &mut suffixes)?;
+        self.expand_into(&section.wiki_suffixes, &mut wiki_suffixes)?;
+
+        rules.suffixes.extend(suffixes.into_keys());
+        rules.wiki_suffixes.extend(wiki_suffixes.into_keys());
+        Ok(())
+    }
+}
+
+impl Rules {
+    /// Read and expand the rules in the file at `path`, see [`parse`](Self::parse).
+    ///
+    /// # Errors
+    ///
+    /// If the file can not be read or contains invalid rules.
+    pub fn load(path: &Path, trailblazer: bool) -> Result<Self, String> {
+        let source = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let file = path
+            .file_name()
+            .unwrap_or(path.as_os_str())
+            .to_string_lossy();
+        Self::parse(&source, &file, trailblazer)
+    }
+
+    /// Expand the rules in `source`. `file` is used to report the origin of entries. The
+    /// `[filter.unless_trailblazer]` rules are skipped for the `trailblazer` profile.
+    ///
+    /// # Errors
+    ///
+    /// If the rules are not valid TOML, contain unknown fields, templates refer to unknown lists
+    /// or an entry is produced by multiple templates.
+    pub fn parse(source: &str, file: &str, trailblazer: bool) -> Result<Self, String> {
+        let parsed: RulesFile = toml::from_str(source).map_err(|e| format!("{}: {}", file, e))?;
+        let expander = Expander {
+            file: file.into(),
+            source,
+            lists: &parsed.lists,
+        };
+
+        let mut rules = Self::default();
+        expander.filter(&parsed.filter, &mut rules)?;
+        if let Some(section) = &parsed.filter.unless_trailblazer {
+            if !trailblazer {
+                expander.filter(section, &mut rules)?;
+            }
+        }
+
+        for aggregation in &parsed.aggregate {
+            let origin = expander.origin(&aggregation.target);
+            let target = aggregation.target.get_ref();
+
+            let mut folds = HashMap::new();
+            expander.expand_into(&aggregation.fold, &mut folds)?;
+            for (name, origin) in folds {
+                if let Some((_, previous)) = rules.folds.get(&name) {
+                    return Err(format!(
+                        "{}: duplicate entry \"{}\" (also at {})",
+                        origin, name, previous
+                    ));
+                }
+                rules.folds.insert(name, (target.clone(), origin));
+            }
+
+            let previous = rules.aggregations.insert(
+                target.clone(),
+                Aggregation {
+                    name: aggregation.name.clone(),
+                    wiki_url: aggregation.wiki_url.clone(),
+                    origin: origin.clone(),
+                },
+            );
+            if let Some(previous) = previous {
+                return Err(format!(
+                    "{}: duplicate aggregation \"{}\" (also at {})",
+                    origin, target, previous.origin
+                ));
+            }
+        }
+
+        let mut pieces = HashMap::new();
+        for section in &parsed.set {
+            let mut items = HashMap::new();
+            expander.expand_into(&section.items, &mut items)?;
+            for (name, origin) in items {
+                if let Some(previous) = pieces.get(&name) {
+                    return Err(format!(
+                        "{}: duplicate entry \"{}\" (also at {})",
+                        origin, name, previous
+                    ));
+                }
+                pieces.insert(name.clone(), origin.clone());
+                rules
+                    .armour_sets
+                    .insert(name, (section.sets.clone(), origin));
+            }
+        }
+
+        Ok(rules)
+    }
+}
+
+/// Expand the placeholders of `template`.
+fn expand(template: &str, lists: &HashMap<String, Vec<String>>) -> Result<Vec<String>, String> {
+    let Some(start) = template.find('{') else {
+        return Ok(vec![template.to_string()]);
+    };
+    let end = template[start..]
+        .find('}')
+        .map(|end| start + end)
+        .ok_or_else(|| format!("unclosed placeholder in \"{}\"", template))?;
+
+    let placeholder = &template[(start + 1)..end];
+    let values: Vec<String> = if let Some((from, to)) = placeholder.split_once("..") {
+        let parse = |s: &str| {
+            s.parse::<u32>()
+                .map_err(|_| format!("invalid range \"{{{}}}\"", placeholder))
+        };
+        (parse(from)?..=parse(to)?).map(|i| i.to_string()).collect()
+    } else {
+        lists
+            .get(placeholder)
+            .ok_or_else(|| format!("unknown list \"{}\"", placeholder))?
+            .clone()
+    };
+
+    let (prefix, rest) = (&template[..start], expand(&template[(end + 1)..], lists)?);
+    Ok(values
+        .iter()
+        .flat_map(|value| {
+            rest.iter()
+                .map(move |r| format!("{}{}{}", prefix, value, r))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+[lists]
+metals = ["Bronze", "Iron"]
+pieces = ["helm", "greataxe"]
+
+[filter]
+names = [
+    "Plain",
+    "{metals} dagger{1..2}",
+]
+suffixes = ["(h{1..3})"]
+
+[[aggregate]]
+target = "Black cape"
+name = "Cape"
+wiki_url = "https://oldschool.runescape.wiki/w/Cape"
+fold = ["{metals} cape"]
+
+[[set]]
+sets = ["Dharok"]
+items = ["Dharok's {pieces}"]
+"#;
+
+    #[test]
+    fn parse() {
+        let rules = Rules::parse(SOURCE, "rules.toml", false).unwrap();
+
+        let mut names: Vec<_> = rules.names.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            [
+                "Bronze dagger1",
+                "Bronze dagger2",
+                "Iron dagger1",
+                "Iron dagger2",
+                "Plain"
+            ]
+        );
+        assert_eq!(rules.names["Plain"].to_string(), "rules.toml:8");
+        assert_eq!(rules.names["Iron dagger2"].line, 9);
+        assert!(rules.suffixes.contains("(h3)"));
+
+        let (target, origin) = &rules.folds["Iron cape"];
+        assert_eq!(target, "Black cape");
+        assert_eq!(origin.line, 17);
+        assert_eq!(rules.aggregations["Black cape"].name, "Cape");
+        assert_eq!(rules.aggregations["Black cape"].origin.line, 14);
+
+        let (sets, origin) = &rules.armour_sets["Dharok's greataxe"];
+        assert_eq!(sets, &[ArmourSet::Dharok]);
+        assert_eq!(origin.line, 21);
+    }
+
+    #[test]
+    fn errors() {
+        let unknown = Rules::parse(
+            "[filter]\nnames = [\"{metal} dagger\"]",
+            "rules.toml",
+            false,
+        );
+        assert_eq!(unknown.unwrap_err(), "rules.toml:2: unknown list \"metal\"");
+
+        let duplicate = Rules::parse("[filter]\nnames = [\"A\",\n\"A\"]", "rules.toml", false);
+        assert_eq!(
+            duplicate.unwrap_err(),
+            "rules.toml:3: duplicate entry \"A\" (also at rules.toml:2)"
+        );
+    }
+
+    #[test]
+    fn rules_file() {
+        let rules = Rules::load(Path::new(RULES_FILE), false).unwrap();
+        assert!(rules.names.contains_key("Rune dagger(p+)"));
+        assert!(rules.wiki_names.contains_key("Rune kiteshield (Zamorak)"));
+        assert_eq!(rules.folds["Zamorak platebody"].0, "Saradomin platebody");
+        assert_eq!(
+            rules.armour_sets["Void ranger helm"].0,
+            [ArmourSet::VoidRanged, ArmourSet::EliteVoidRanged]
+        );
+
+        let trailblazer = Rules::load(Path::new(RULES_FILE), true).unwrap();
+        assert!(rules.names.contains_key("Enchanted hat"));
+        assert!(!trailblazer.names.contains_key("Enchanted hat"));
+        assert!(!trailblazer.suffixes.contains("(dark)"));
+    }
+}
@@ -0,0 +1,278 @@
+//! Item data sources and merging them into OSRSBox items.
+//!
+//! Every source produces partial items in the OSRSBox format (see [osrsbox](crate::osrsbox)),
+//! which are merged by item id. Sources later in the list take precedence field by field, nested
+//! objects like `equipment` are merged recursively and `null` never replaces a value. Differing
+//! values are recorded as [`Conflict`]s.
+//!
+//! The default precedence is [`OsrsBox`] < [`WikiExport`] < [`Overrides`].
+
+mod osrsbox;
+mod overrides;
+mod wiki;
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::{json, Map, Value};
+
+pub use osrsbox::OsrsBox;
+pub use overrides::{Overrides, OVERRIDES_FILE};
+pub use wiki::WikiExport;
+
+use crate::explain::Conflict;
+use crate::osrsbox::ItemProperties;
+use crate::rules::Origin;
+
+/// Partial item of a source.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    /// Item id.
+    pub id: u32,
+    /// Fields in the OSRSBox format.
+    pub fields: Map<String, Value>,
+    /// Location of the record, reported instead of the source name if known.
+    pub origin: Option<Origin>,
+}
+
+/// A source of item data.
+pub trait ItemSource {
+    /// Name of the source in the report, e.g. `osrsbox`.
+    fn name(&self) -> &str;
+
+    /// Read the partial items of the source.
+    ///
+    /// # Errors
+    ///
+    /// If the source can not be read or is invalid.
+    fn records(&self) -> Result<Vec<Record>, String>;
+}
+
+/// The merged items of all sources.
+#[derive(Default)]
+pub struct Merged {
+    /// Merged items, sorted by id.
+    pub items: Vec<ItemProperties>,
+    /// Values replaced by a source with higher precedence.
+    pub conflicts: Vec<Conflict>,
+    /// Merged items which are not valid OSRSBox items.
+    pub errors: Vec<String>,
+}
+
+/// Non-optional OSRSBox fields, used for items which are missing them in all sources.
+fn defaults() -> Value {
+    json!({
+        "last_updated": "",
+        "incomplete": false,
+        "members": false,
+        "tradeable": false,
+        "tradeable_on_ge": false,
+        "stackable": false,
+        "noted": false,
+        "noteable": false,
+        "placeholder": false,
+        "equipable": true,
+        "equipable_by_player": true,
+        "equipable_weapon": false,
+        "cost": 0,
+        "quest_item": false,
+        "duplicate": false,
+        "icon": "",
+    })
+}
+
+/// A merged item with the source of every field.
+#[derive(Default)]
+struct Entry {
+    fields: Map<String, Value>,
+    sources: HashMap<String, String>,
+}
+
+impl Entry {
+    /// Merge `fields` from `source` into the entry, recording conflicts.
+    fn overlay(
+        &mut self,
+        id: u32,
+        fields: Map<String, Value>,
+        source: &str,
+        conflicts: &mut Vec<Conflict>,
+    ) {
+        let Self {
+            fields: root,
+            sources,
+        } = self;
+
+        let mut stack = vec![(String::new(), fields)];
+        while let Some((prefix, fields)) = stack.pop() {
+            let mut target = &mut *root;
+            for key in prefix.split('.').filter(|k| !k.is_empty()) {
+                target = target.get_mut(key).and_then(Value::as_object_mut).unwrap();
+            }
+
+            for (key, value) in fields {
+                let path = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", prefix, key)
+                };
+                match (target.get_mut(&key), value) {
+                    (_, Value::Null) => {}
+                    (Some(Value::Object(_)), Value::Object(value)) => stack.push((path, value)),
+                    (Some(previous), value) => {
+                        if *previous != value {
+                            let previous_source = sources.get(&path).cloned();
+                            conflicts.push(Conflict {
+                                id,
+                                name: None,
+                                field: path.clone(),
+                                source: source.to_string(),
+                                value: value.clone(),
+                                previous_source: previous_source.unwrap_or_default(),
+                                previous: previous.clone(),
+                            });
+                        }
+                        *previous = value;
+                        record(sources, &path, previous, source);
+                    }
+                    (None, value) => {
+                        record(sources, &path, &value, source);
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Record `source` for `path` and all nested fields of `value`.
+fn record(sources: &mut HashMap<String, String>, path: &str, value: &Value, source: &str) {
+    if let Value::Object(fields) = value {
+        for (key, value) in fields {
+            record(sources, &format!("{}.{}", path, key), value, source);
+        }
+    }
+    sources.insert(path.to_string(), source.to_string());
+}
+
+/// Merge the items of `sources` by id, in increasing order of precedence.
+///
+/// # Errors
+///
+/// If a source can not be read.
+pub fn merge(sources: &[&dyn ItemSource]) -> Result<Merged, String> {
+    let mut entries: BTreeMap<u32, Entry> = BTreeMap::new();
+    let mut conflicts = Vec::new();
+
+    for source in sources {
+        for record in source.records()? {
+            let name = record
+                .origin
+                .as_ref()
+                .map_or_else(|| source.name().to_string(), ToString::to_string);
+            entries.entry(record.id).or_default().overlay(
+                record.id,
+                record.fields,
+                &name,
+                &mut conflicts,
+            );
+        }
+    }
+
+    let mut merged = Merged::default();
+    let mut names = HashMap::new();
+    for (id, entry) in entries {
+        let mut fields = entry.fields;
+        if let Value::Object(defaults) = defaults() {
+            for (key, value) in defaults {
+                fields.entry(key).or_insert(value);
+            }
+        }
+        fields.insert("id".to_string(), Value::from(id));
+
+        match serde_json::from_value::<ItemProperties>(Value::Object(fields)) {
+            Ok(item) => {
+                names.insert(id, item.name.clone());
+                merged.items.push(item);
+            }
+            Err(e) => merged
+                .errors
+                .push(format!("Invalid merged item {}: {}", id, e)),
+        }
+    }
+
+    for mut conflict in conflicts {
+        conflict.name = names.get(&conflict.id).cloned();
+        merged.conflicts.push(conflict);
+    }
+
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osrsbox::HELM_OF_NEITIZNOT;
+
+    struct Static(&'static str, Vec<Record>);
+
+    impl ItemSource for Static {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn records(&self) -> Result<Vec<Record>, String> {
+            Ok(self.1.clone())
+        }
+    }
+
+    fn record(id: u32, fields: Value) -> Record {
+        Record {
+            id,
+            fields: fields.as_object().unwrap().clone(),
+            origin: None,
+        }
+    }
+
+    #[test]
+    fn merge() {
+        let base = Static(
+            "base",
+            OsrsBox::parse(&format!("{{\"10828\": {}}}", HELM_OF_NEITIZNOT)).unwrap(),
+        );
+        let wiki = Static(
+            "wiki",
+            vec![
+                record(
+                    10828,
+                    json!({"name": "Helm of neitiznot", "weight": null, "equipment": {"prayer": 4}}),
+                ),
+                record(
+                    30000,
+                    json!({"name": "New helm", "equipment": {"slot": "head", "prayer": 1}}),
+                ),
+            ],
+        );
+        let overrides = Static("overrides", vec![record(10828, json!({"weight": 2.0}))]);
+
+        let merged = super::merge(&[&base, &wiki, &overrides]).unwrap();
+        assert_eq!(merged.items.len(), 1);
+        assert_eq!(merged.errors.len(), 1);
+        assert!(merged.errors[0].starts_with("Invalid merged item 30000:"));
+
+        let helm = &merged.items[0];
+        assert_eq!(helm.weight, Some(2.0));
+        let equipment = helm.equipment.as_ref().unwrap();
+        assert_eq!((equipment.prayer, equipment.defence_stab), (4, 31));
+
+        assert_eq!(merged.conflicts.len(), 2);
+        let conflict = &merged.conflicts[0];
+        assert_eq!(conflict.name.as_deref(), Some("Helm of neitiznot"));
+        assert_eq!(conflict.field, "equipment.prayer");
+        assert_eq!(
+            (conflict.source.as_str(), &conflict.value),
+            ("wiki", &json!(4))
+        );
+        assert_eq!(conflict.previous_source, "base");
+        assert_eq!(merged.conflicts[1].field, "weight");
+        assert_eq!(merged.conflicts[1].previous_source, "base");
+    }
+}
@@ -0,0 +1,59 @@
+//! OSRSBox `items-complete.json`.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{Map, Value};
+
+use super::{ItemSource, Record};
+
+/// Items of the [OSRSBox](https://www.osrsbox.com/) static JSON API, which is no longer updated.
+#[derive(Debug, Clone)]
+pub struct OsrsBox {
+    path: PathBuf,
+}
+
+impl OsrsBox {
+    /// Source reading the items from the file at `path`.
+    #[must_use]
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// Parse the items in `json`, a map of item ids to items.
+    ///
+    /// # Errors
+    ///
+    /// If `json` is not a map of item ids to objects.
+    pub fn parse(json: &str) -> Result<Vec<Record>, String> {
+        let items: HashMap<u32, Map<String, Value>> =
+            serde_json::from_str(json).map_err(|e| e.to_string())?;
+        Ok(items
+            .into_iter()
+            .map(|(id, fields)| Record {
+                id,
+                fields,
+                origin: None,
+            })
+            .collect())
+    }
+}
+
+impl ItemSource for OsrsBox {
+    fn name(&self) -> &'static str {
+        "osrsbox"
+    }
+
+    fn records(&self) -> Result<Vec<Record>, String> {
+        // Using serde_json::from_reader is slower than this
+        // (see https://github.com/serde-rs/json/issues/160)
+        let json = fs::read_to_string(&self.path)
+            .map_err(|e| format!("{}: {}", self.path.display(), e))?;
+        Self::parse(&json).map_err(|e| format!("{}: {}", self.path.display(), e))
+    }
+}
@@ -0,0 +1,181 @@
+//! Manual corrections of item data, loaded from `overrides.toml`.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use data::EquipSlot;
+use serde::Deserialize;
+use serde_json::Map;
+use toml::Spanned;
+
+use super::{ItemSource, Record};
+use crate::rules::Origin;
+
+/// Path of the overrides file in the source tree, the default of `--overrides`.
+pub const OVERRIDES_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/overrides.toml");
+
+/// OSRSBox equipment stats which can be overridden.
+const STATS: &[&str] = &[
+    "attack_stab",
+    "attack_slash",
+    "attack_crush",
+    "attack_magic",
+    "attack_ranged",
+    "defence_stab",
+    "defence_slash",
+    "defence_crush",
+    "defence_magic",
+    "defence_ranged",
+    "melee_strength",
+    "ranged_strength",
+    "magic_damage",
+    "prayer",
+];
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OverridesFile {
+    #[serde(default)]
+    item: Vec<ItemOverride>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ItemOverride {
+    id: Spanned<u32>,
+    weight: Option<f64>,
+    slot: Option<EquipSlot>,
+    #[serde(default)]
+    stats: BTreeMap<String, i16>,
+}
+
+/// Corrections applied over all other sources.
+#[derive(Debug, Clone)]
+pub struct Overrides {
+    path: PathBuf,
+}
+
+impl Overrides {
+    /// Source reading the overrides from the file at `path`.
+    #[must_use]
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// Parse the overrides in `source`. `file` is used to report the origin of overrides.
+    ///
+    /// # Errors
+    ///
+    /// If the overrides are not valid TOML, contain unknown fields or stats.
+    ///
+    /// # Panics
+    ///
+    /// Never.
+    pub fn parse(source: &str, file: &str) -> Result<Vec<Record>, String> {
+        let parsed: OverridesFile =
+            toml::from_str(source).map_err(|e| format!("{}: {}", file, e))?;
+        let file: Arc<str> = file.into();
+
+        parsed
+            .item
+            .into_iter()
+            .map(|item| {
+                let origin = Origin {
+                    file: file.clone(),
+                    line: source[..item.id.start()].matches('\n').count() + 1,
+                };
+
+                let mut equipment = Map::new();
+                for (stat, value) in item.stats {
+                    if !STATS.contains(&stat.as_str()) {
+                        return Err(format!("{}: unknown stat \"{}\"", origin, stat));
+                    }
+                    equipment.insert(stat, value.into());
+                }
+                if let Some(slot) = item.slot {
+                    equipment.insert("slot".to_string(), serde_json::to_value(slot).unwrap());
+                }
+
+                let mut fields = Map::new();
+                if let Some(weight) = item.weight {
+                    fields.insert("weight".to_string(), weight.into());
+                }
+                if !equipment.is_empty() {
+                    fields.insert("equipment".to_string(), equipment.into());
+                }
+
+                Ok(Record {
+                    id: item.id.into_inner(),
+                    fields,
+                    origin: Some(origin),
+                })
+            })
+            .collect()
+    }
+}
+
+impl ItemSource for Overrides {
+    fn name(&self) -> &'static str {
+        "overrides"
+    }
+
+    fn records(&self) -> Result<Vec<Record>, String> {
+        let source = fs::read_to_string(&self.path)
+            .map_err(|e| format!("{}: {}", self.path.display(), e))?;
+        let file = self
+            .path
+            .file_name()
+            .unwrap_or(self.path.as_os_str())
+            .to_string_lossy();
+        Self::parse(&source, &file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        const SOURCE: &str = r#"
+[[item]]
+id = 10828
+weight = 2.5
+slot = "2h"
+stats = { prayer = 4, defence_stab = -1 }
+
+[[item]]
+id = 4151
+"#;
+
+        let records = Overrides::parse(SOURCE, "overrides.toml").unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].id, 10828);
+        assert_eq!(
+            records[0].origin.as_ref().unwrap().to_string(),
+            "overrides.toml:3"
+        );
+        assert_eq!(
+            Value::Object(records[0].fields.clone()),
+            json!({"weight": 2.5, "equipment": {"prayer": 4, "defence_stab": -1, "slot": "2h"}})
+        );
+        assert!(records[1].fields.is_empty());
+
+        let error = Overrides::parse("[[item]]\nid = 1\nstats = { luck = 1 }", "o.toml");
+        assert_eq!(error, Err("o.toml:2: unknown stat \"luck\"".to_string()));
+        assert!(Overrides::parse("[[item]]\nid = 1\nname = \"A\"", "o.toml").is_err());
+    }
+
+    #[test]
+    fn overrides_file() {
+        Overrides::new(Path::new(OVERRIDES_FILE)).records().unwrap();
+    }
+}
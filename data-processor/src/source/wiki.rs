@@ -0,0 +1,214 @@
+//! Item export of the [OSRS Wiki](https://oldschool.runescape.wiki/).
+//!
+//! The export is the JSON response of a [Bucket](https://oldschool.runescape.wiki/w/Special:Bucket)
+//! query of `infobox_item` joined with `infobox_bonuses`, with the joined field names unprefixed:
+//!
+//! ```json
+//! {"bucket": [{"item_id": ["10828"], "item_name": "Helm of neitiznot",
+//!   "page_name": "Helm of neitiznot", "weight": 2.267, "prayer_bonus": 3, ...}]}
+//! ```
+//!
+//! Records with several item ids apply to all of them. Weapon stances are not part of the export,
+//! so new weapons need them from an override. Icons are only included if the export contains an
+//! `icon` field with base64 PNG data.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use super::{ItemSource, Record};
+
+/// Base URL of wiki pages.
+const WIKI_URL: &str = "https://oldschool.runescape.wiki/w/";
+
+/// Bonus fields of `infobox_bonuses` and the corresponding OSRSBox equipment fields.
+const BONUSES: &[(&str, &str)] = &[
+    ("stab_attack_bonus", "attack_stab"),
+    ("slash_attack_bonus", "attack_slash"),
+    ("crush_attack_bonus", "attack_crush"),
+    ("magic_attack_bonus", "attack_magic"),
+    ("range_attack_bonus", "attack_ranged"),
+    ("stab_defence_bonus", "defence_stab"),
+    ("slash_defence_bonus", "defence_slash"),
+    ("crush_defence_bonus", "defence_crush"),
+    ("magic_defence_bonus", "defence_magic"),
+    ("range_defence_bonus", "defence_ranged"),
+    ("strength_bonus", "melee_strength"),
+    ("ranged_strength_bonus", "ranged_strength"),
+    ("magic_damage_bonus", "magic_damage"),
+    ("prayer_bonus", "prayer"),
+    ("equipment_slot", "slot"),
+];
+
+#[derive(Deserialize)]
+struct Export {
+    bucket: Vec<Map<String, Value>>,
+}
+
+/// Items of an OSRS Wiki export file.
+#[derive(Debug, Clone)]
+pub struct WikiExport {
+    path: PathBuf,
+}
+
+impl WikiExport {
+    /// Source reading the items from the file at `path`.
+    #[must_use]
+    pub fn new(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+        }
+    }
+
+    /// Parse the items in `json`.
+    ///
+    /// # Errors
+    ///
+    /// If `json` is not a bucket response or a record has no valid item ids or page name.
+    pub fn parse(json: &str) -> Result<Vec<Record>, String> {
+        let export: Export = serde_json::from_str(json).map_err(|e| e.to_string())?;
+
+        let mut records = Vec::new();
+        for (i, mut row) in export.bucket.into_iter().enumerate() {
+            let ids = item_ids(row.get("item_id"))
+                .ok_or_else(|| format!("record {}: missing or invalid item_id", i))?;
+            let Some(Value::String(page_name)) = row.remove("page_name") else {
+                return Err(format!("record {}: missing page_name", i));
+            };
+
+            let mut fields = Map::new();
+            let mut wiki_name = page_name.clone();
+            let mut wiki_url = format!("{}{}", WIKI_URL, page_name.replace(' ', "_"));
+            if let Some(Value::String(version)) = row.remove("version_anchor") {
+                wiki_name = format!("{} ({})", wiki_name, version);
+                wiki_url = format!("{}#{}", wiki_url, version.replace(' ', "_"));
+            }
+            fields.insert("wiki_name".to_string(), wiki_name.into());
+            fields.insert("wiki_url".to_string(), wiki_url.into());
+
+            for (field, name) in [
+                ("item_name", "name"),
+                ("is_members_only", "members"),
+                ("weight", "weight"),
+                ("icon", "icon"),
+            ] {
+                if let Some(value) = row.remove(field) {
+                    fields.insert(name.to_string(), value);
+                }
+            }
+
+            let equipment: Map<_, _> = BONUSES
+                .iter()
+                .filter_map(|(field, name)| Some((name.to_string(), row.remove(*field)?)))
+                .filter(|(_, value)| !value.is_null())
+                .collect();
+            if !equipment.is_empty() {
+                fields.insert("equipment".to_string(), equipment.into());
+            }
+
+            if let Some(speed) = row.remove("weapon_attack_speed").filter(|v| !v.is_null()) {
+                let mut weapon = Map::new();
+                weapon.insert("attack_speed".to_string(), speed);
+                fields.insert("weapon".to_string(), weapon.into());
+            }
+
+            records.extend(ids.into_iter().map(|id| Record {
+                id,
+                fields: fields.clone(),
+                origin: None,
+            }));
+        }
+
+        Ok(records)
+    }
+}
+
+/// Item ids of a record, which are a list of strings in bucket responses.
+fn item_ids(value: Option<&Value>) -> Option<Vec<u32>> {
+    value?
+        .as_array()?
+        .iter()
+        .map(|id| match id {
+            Value::String(id) => id.trim().parse().ok(),
+            id => id.as_u64()?.try_into().ok(),
+        })
+        .collect()
+}
+
+impl ItemSource for WikiExport {
+    fn name(&self) -> &'static str {
+        "wiki"
+    }
+
+    fn records(&self) -> Result<Vec<Record>, String> {
+        let json = fs::read_to_string(&self.path)
+            .map_err(|e| format!("{}: {}", self.path.display(), e))?;
+        Self::parse(&json).map_err(|e| format!("{}: {}", self.path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let json = json!({"bucket": [
+            {
+                "item_id": ["11335", "20000"],
+                "item_name": "Dragon full helm",
+                "page_name": "Dragon full helm",
+                "is_members_only": true,
+                "weight": 2.721,
+                "slash_defence_bonus": 47,
+                "prayer_bonus": null,
+                "equipment_slot": "head",
+            },
+            {
+                "item_id": ["4151"],
+                "item_name": "Abyssal whip",
+                "page_name": "Abyssal whip",
+                "version_anchor": "Beta",
+                "weapon_attack_speed": 4,
+            },
+        ]});
+        let records = WikiExport::parse(&json.to_string()).unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].id, 11335);
+        assert_eq!(records[1].id, 20000);
+        assert_eq!(records[0].fields, records[1].fields);
+        assert_eq!(
+            Value::Object(records[0].fields.clone()),
+            json!({
+                "name": "Dragon full helm",
+                "wiki_name": "Dragon full helm",
+                "wiki_url": "https://oldschool.runescape.wiki/w/Dragon_full_helm",
+                "members": true,
+                "weight": 2.721,
+                "equipment": {"defence_slash": 47, "slot": "head"},
+            })
+        );
+
+        let whip = &records[2].fields;
+        assert_eq!(whip["wiki_name"], "Abyssal whip (Beta)");
+        assert_eq!(
+            whip["wiki_url"],
+            "https://oldschool.runescape.wiki/w/Abyssal_whip#Beta"
+        );
+        assert_eq!(whip["weapon"], json!({"attack_speed": 4}));
+        assert!(!whip.contains_key("equipment"));
+
+        let error = WikiExport::parse(r#"{"bucket": [{"item_id": ["x"], "page_name": "A"}]}"#);
+        assert_eq!(
+            error,
+            Err("record 0: missing or invalid item_id".to_string())
+        );
+    }
+}
@@ -0,0 +1,271 @@
+//! Detect kept items with identical stats, which are candidates for aggregation.
+//!
+//! Many items only differ in appearance (god variants, ornament kits, camo gear). Items covered by
+//! the filters or aggregations of `rules.toml` (see [rules](crate::rules)) are gone after the
+//! pipeline, so the remaining groups of identical items are variants which were missed, e.g. after
+//! a game update. For each group, a rule is suggested.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Display},
+};
+
+use data::{Database, EquipSlot, Item};
+
+use crate::explain::{Fate, Report};
+
+/// One item of a [`Cluster`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    /// Item id.
+    pub id: u32,
+    /// Name of the item in the output.
+    pub name: String,
+    /// OSRSBox wiki name, as used in the rules.
+    pub wiki_name: Option<String>,
+    /// Wiki URL of the item in the output.
+    pub wiki_url: String,
+    /// Whether the item is already an aggregate of other items.
+    pub aggregated: bool,
+}
+
+/// Kept items with identical stats, slot and requirements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cluster {
+    /// Equipment slot of the items.
+    pub slot: EquipSlot,
+    /// The items, sorted by id.
+    pub variants: Vec<Variant>,
+}
+
+impl Cluster {
+    /// The item the others should be folded into: an existing aggregate, the item named like the
+    /// [stem](Self::stem), or the first item.
+    ///
+    /// # Panics
+    ///
+    /// Never.
+    #[must_use]
+    pub fn target(&self) -> &Variant {
+        let stem = self.stem();
+        self.variants
+            .iter()
+            .find(|v| v.aggregated)
+            .or_else(|| self.variants.iter().find(|v| v.name == stem))
+            .unwrap_or(&self.variants[0])
+    }
+
+    /// Proposed name of the aggregate, see [`stem`].
+    #[must_use]
+    pub fn stem(&self) -> String {
+        let names: Vec<_> = self.variants.iter().map(|v| v.name.as_str()).collect();
+        stem(&names)
+    }
+
+    /// Rule for `rules.toml` folding the other items into the [target](Self::target).
+    ///
+    /// An existing aggregate only needs the other items added to its `fold` list.
+    #[must_use]
+    pub fn suggestion(&self) -> String {
+        let target = self.target();
+        let fold: Vec<_> = self
+            .variants
+            .iter()
+            .filter(|v| v.id != target.id)
+            .map(|v| format!("{:?}", v.wiki_name.as_ref().unwrap_or(&v.name)))
+            .collect();
+
+        if target.aggregated {
+            return format!(
+                "Add to the fold list of {}: {}",
+                target.name,
+                fold.join(", ")
+            );
+        }
+        format!(
+            "[[aggregate]]\ntarget = {:?}\nname = {:?}\nwiki_url = {:?}\nfold = [{}]",
+            target.wiki_name.as_ref().unwrap_or(&target.name),
+            self.stem(),
+            target.wiki_url,
+            fold.join(", ")
+        )
+    }
+}
+
+impl Display for Cluster {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<_> = self
+            .variants
+            .iter()
+            .map(|v| format!("{} (id {})", v.name, v.id))
+            .collect();
+        write!(f, "{:?}: {}", self.slot, names.join(", "))
+    }
+}
+
+/// Everything the optimizer compares, serialized to compare items without `PartialEq`.
+///
+/// # Panics
+///
+/// Never, the item data only contains types which serialize to JSON.
+fn key(item: &Item) -> String {
+    serde_json::to_string(&(
+        &item.combat_stats,
+        &item.conditional_bonuses,
+        &item.armour_sets,
+        &item.weapon_data,
+        item.equip_slot,
+        &item.requirements,
+    ))
+    .expect("item data is serializable")
+}
+
+/// Group the items of `database` with identical combat stats, weapon data, set effects, slot and
+/// requirements. Only groups of more than one item are returned, sorted by slot and name of the
+/// first item. Wiki names and aggregates are taken from the `report` of the same run.
+#[must_use]
+pub fn clusters(database: &Database, report: &Report) -> Vec<Cluster> {
+    let traces: HashMap<_, _> = report.iter().map(|t| (t.id, t)).collect();
+
+    let mut groups: BTreeMap<String, Vec<&Item>> = BTreeMap::new();
+    for item in database.iter() {
+        groups.entry(key(item)).or_default().push(item);
+    }
+
+    let mut clusters: Vec<_> = groups
+        .into_values()
+        .filter(|items| items.len() > 1)
+        .map(|mut items| {
+            items.sort_unstable_by_key(|item| item.id);
+            Cluster {
+                slot: items[0].equip_slot,
+                variants: items
+                    .into_iter()
+                    .map(|item| {
+                        let trace = traces.get(&item.id);
+                        Variant {
+                            id: item.id,
+                            name: item.name.clone(),
+                            wiki_name: trace.and_then(|t| t.wiki_name.clone()),
+                            wiki_url: item.wiki_url.clone(),
+                            aggregated: trace
+                                .is_some_and(|t| matches!(t.fate, Fate::Aggregated { .. })),
+                        }
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+    clusters.sort_by(|a, b| {
+        (a.slot as u8, &a.variants[0].name).cmp(&(b.slot as u8, &b.variants[0].name))
+    });
+    clusters
+}
+
+/// Common part of `names`: the words they all start with (e.g. `Dragon platebody` for ornament
+/// kits), otherwise the words they all end with (e.g. `Cape` for `Black cape` and `Team-1 cape`).
+/// Falls back to the first name if the names have nothing in common.
+#[must_use]
+pub fn stem(names: &[&str]) -> String {
+    let words: Vec<Vec<&str>> = names.iter().map(|n| n.split(' ').collect()).collect();
+    let Some(first) = words.first() else {
+        return String::new();
+    };
+    let same = |a: &&str, b: &&str| a.eq_ignore_ascii_case(b);
+
+    let prefix = (0..first.len())
+        .take_while(|&i| {
+            words
+                .iter()
+                .all(|w| w.get(i).is_some_and(|w| same(w, &first[i])))
+        })
+        .count();
+    if prefix > 0 {
+        return first[..prefix].join(" ");
+    }
+
+    let suffix = (1..=first.len())
+        .take_while(|&i| {
+            let word = &first[first.len() - i];
+            words
+                .iter()
+                .all(|w| w.len() >= i && same(&w[w.len() - i], word))
+        })
+        .count();
+    if suffix > 0 {
+        let stem = first[first.len() - suffix..].join(" ");
+        let mut chars = stem.chars();
+        return chars
+            .next()
+            .map(|c| c.to_uppercase().chain(chars).collect())
+            .unwrap_or_default();
+    }
+
+    names[0].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::osrsbox::helm as item;
+    use crate::pipeline::Pipeline;
+    use crate::rules::Rules;
+
+    const RULES: &str = r#"
+[[aggregate]]
+target = "Saradomin helm"
+name = "God helm"
+wiki_url = "https://oldschool.runescape.wiki/w/God_helm"
+fold = ["Zamorak helm"]
+"#;
+
+    #[test]
+    fn stems() {
+        assert_eq!(
+            stem(&["Dragon platebody", "Dragon platebody (g)"]),
+            "Dragon platebody"
+        );
+        assert_eq!(stem(&["Black cape", "Team-1 cape", "Team-2 cape"]), "Cape");
+        assert_eq!(
+            stem(&["Abyssal whip", "Volcanic abyssal whip"]),
+            "Abyssal whip"
+        );
+        assert_eq!(stem(&["Fire cape", "Infernal max cape"]), "Cape");
+        assert_eq!(stem(&["Ring", "Amulet"]), "Ring");
+    }
+
+    #[test]
+    fn clusters() {
+        let pipeline = Pipeline::new(&Rules::parse(RULES, "rules.toml", false).unwrap());
+        let (database, report) = pipeline.process(vec![
+            item(1, "Saradomin helm", 3),
+            item(2, "Zamorak helm", 3),
+            item(3, "Guthix helm", 3),
+            item(4, "Red helm", 1),
+            item(5, "Red helm (g)", 1),
+            item(6, "Other helm", 2),
+        ]);
+        let clusters = super::clusters(&database, &report);
+        assert_eq!(clusters.len(), 2);
+
+        let gods = &clusters[0];
+        assert_eq!(gods.slot, EquipSlot::Head);
+        let ids: Vec<_> = gods.variants.iter().map(|v| v.id).collect();
+        assert_eq!(ids, [1, 3]);
+        assert_eq!(gods.target().name, "God helm");
+        assert_eq!(
+            gods.suggestion(),
+            "Add to the fold list of God helm: \"Guthix helm\""
+        );
+
+        let red = &clusters[1];
+        assert_eq!(red.stem(), "Red helm");
+        assert_eq!(red.target().id, 4);
+        assert_eq!(
+            red.suggestion(),
+            "[[aggregate]]\ntarget = \"Red helm\"\nname = \"Red helm\"\n\
+             wiki_url = \"https://oldschool.runescape.wiki/w/Helm_of_neitiznot\"\n\
+             fold = [\"Red helm (g)\"]"
+        );
+    }
+}
@@ -0,0 +1,512 @@
+use enum_iterator::IntoEnumIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    item::apply_multipliers, CombatClass, CombatStats, DamageType, EquipSlot, Item, Loadout,
+    Multiplier,
+};
+
+/// Armour sets with an effect when (partially) equipped.
+///
+/// Void knight equipment is split up by the helm worn, since the helm determines the effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, IntoEnumIterator)]
+pub enum ArmourSet {
+    /// [Void knight equipment](https://oldschool.runescape.wiki/w/Void_Knight_equipment) with a
+    /// melee helm.
+    VoidMelee,
+    /// Void knight equipment with a ranger helm.
+    VoidRanged,
+    /// Void knight equipment with a mage helm.
+    VoidMagic,
+    /// Elite void knight equipment with a ranger helm.
+    EliteVoidRanged,
+    /// Elite void knight equipment with a mage helm.
+    EliteVoidMagic,
+    /// [Obsidian armour](https://oldschool.runescape.wiki/w/Obsidian_armour) with an obsidian
+    /// melee weapon.
+    Obsidian,
+    /// [Inquisitor's armour](https://oldschool.runescape.wiki/w/Inquisitor%27s_armour).
+    Inquisitor,
+    /// [Crystal armour](https://oldschool.runescape.wiki/w/Crystal_armour) with a crystal bow.
+    Crystal,
+    /// [Justiciar armour](https://oldschool.runescape.wiki/w/Justiciar_armour).
+    Justiciar,
+    /// [Ahrim's equipment](https://oldschool.runescape.wiki/w/Ahrim_the_Blighted%27s_equipment).
+    Ahrim,
+    /// [Dharok's equipment](https://oldschool.runescape.wiki/w/Dharok_the_Wretched%27s_equipment).
+    Dharok,
+    /// [Guthan's equipment](https://oldschool.runescape.wiki/w/Guthan_the_Infested%27s_equipment).
+    Guthan,
+    /// [Karil's equipment](https://oldschool.runescape.wiki/w/Karil_the_Tainted%27s_equipment).
+    Karil,
+    /// [Torag's equipment](https://oldschool.runescape.wiki/w/Torag_the_Corrupted%27s_equipment).
+    Torag,
+    /// [Verac's equipment](https://oldschool.runescape.wiki/w/Verac_the_Defiled%27s_equipment).
+    Verac,
+}
+
+impl ArmourSet {
+    /// Slots which have to be filled with pieces of the set for the full effect. Two handed
+    /// weapons count for the weapon slot.
+    #[must_use]
+    pub fn slots(self) -> &'static [EquipSlot] {
+        use EquipSlot::{Body, Hands, Head, Legs, Weapon};
+
+        match self {
+            Self::VoidMelee
+            | Self::VoidRanged
+            | Self::VoidMagic
+            | Self::EliteVoidRanged
+            | Self::EliteVoidMagic => &[Head, Body, Legs, Hands],
+            Self::Inquisitor | Self::Justiciar => &[Head, Body, Legs],
+            Self::Obsidian
+            | Self::Crystal
+            | Self::Ahrim
+            | Self::Dharok
+            | Self::Guthan
+            | Self::Karil
+            | Self::Torag
+            | Self::Verac => &[Head, Body, Legs, Weapon],
+        }
+    }
+
+    /// Short description of the effect.
+    #[must_use]
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::VoidMelee | Self::Obsidian => "+10% melee accuracy and damage",
+            Self::VoidRanged => "+10% ranged accuracy and damage",
+            Self::VoidMagic => "+45% magic accuracy",
+            Self::EliteVoidRanged => "+10% ranged accuracy, +12.5% ranged damage",
+            Self::EliteVoidMagic => "+45% magic accuracy, +2.5% magic damage",
+            Self::Inquisitor => "+0.5% crush accuracy and damage per piece, +2.5% for the full set",
+            Self::Crystal => "Up to +30% ranged accuracy and +15% ranged damage with a crystal bow",
+            Self::Justiciar => "Reduces damage taken based on the defence bonus",
+            Self::Ahrim => "Chance to lower the target's strength when casting",
+            Self::Dharok => "Damage increases as hitpoints decrease",
+            Self::Guthan => "Chance to heal by the damage dealt",
+            Self::Karil => "Chance to lower the target's agility",
+            Self::Torag => "Chance to drain the target's run energy",
+            Self::Verac => "Chance to ignore defence and protection prayers",
+        }
+    }
+
+    /// Returns `true` if the set effect boosts attacks of `damage_type`.
+    #[must_use]
+    pub fn boosts(self, damage_type: DamageType) -> bool {
+        match self {
+            Self::VoidMelee | Self::Obsidian | Self::Dharok => {
+                damage_type.class() == CombatClass::Melee
+            }
+            Self::Inquisitor => damage_type == DamageType::Crush,
+            Self::VoidRanged | Self::EliteVoidRanged | Self::Crystal => {
+                damage_type == DamageType::Ranged
+            }
+            Self::VoidMagic | Self::EliteVoidMagic => damage_type == DamageType::Magic,
+            _ => false,
+        }
+    }
+
+    /// A set which provides a weaker version of the same effect, which does not stack.
+    fn supersedes(self) -> Option<Self> {
+        match self {
+            Self::EliteVoidRanged => Some(Self::VoidRanged),
+            Self::EliteVoidMagic => Some(Self::VoidMagic),
+            _ => None,
+        }
+    }
+
+    /// Determine the effect from the equipped `pieces` of this set, if the set is complete enough.
+    fn bonus(self, pieces: &[&Item]) -> Option<SetBonus> {
+        let has = |slot: EquipSlot| {
+            pieces.iter().any(|i| match i.equip_slot {
+                EquipSlot::TwoHanded => slot == EquipSlot::Weapon,
+                s => s == slot,
+            })
+        };
+        let complete = self.slots().iter().all(|&s| has(s));
+
+        let (class, damage_types, accuracy, damage) = match self {
+            Self::Inquisitor => {
+                let numerator = match pieces.len() {
+                    0 => return None,
+                    _ if complete => 1025,
+                    n => 1000 + 5 * u16::try_from(n).unwrap_or(0),
+                };
+                let multiplier = Multiplier::new(numerator, 1000);
+                (
+                    CombatClass::Melee,
+                    vec![DamageType::Crush],
+                    multiplier,
+                    multiplier,
+                )
+            }
+            Self::Crystal => {
+                let armour = [EquipSlot::Head, EquipSlot::Body, EquipSlot::Legs];
+                if !has(EquipSlot::Weapon) || !armour.iter().any(|&s| has(s)) {
+                    return None;
+                }
+
+                // Helm: 5% / 2.5%, body: 15% / 7.5%, legs: 10% / 5%
+                let weight = |slot, value| if has(slot) { value } else { 0 };
+                let accuracy = weight(EquipSlot::Head, 50)
+                    + weight(EquipSlot::Body, 150)
+                    + weight(EquipSlot::Legs, 100);
+                (
+                    CombatClass::Ranged,
+                    vec![DamageType::Ranged],
+                    Multiplier::new(1000 + accuracy, 1000),
+                    Multiplier::new(1000 + accuracy / 2, 1000),
+                )
+            }
+            _ if !complete => return None,
+            Self::VoidMelee | Self::Obsidian => {
+                let multiplier = Multiplier::new(11, 10);
+                (
+                    CombatClass::Melee,
+                    CombatClass::Melee.damage_types().collect(),
+                    multiplier,
+                    multiplier,
+                )
+            }
+            Self::VoidRanged => (
+                CombatClass::Ranged,
+                vec![DamageType::Ranged],
+                Multiplier::new(11, 10),
+                Multiplier::new(11, 10),
+            ),
+            Self::EliteVoidRanged => (
+                CombatClass::Ranged,
+                vec![DamageType::Ranged],
+                Multiplier::new(11, 10),
+                Multiplier::new(9, 8),
+            ),
+            Self::VoidMagic => (
+                CombatClass::Magic,
+                vec![DamageType::Magic],
+                Multiplier::new(29, 20),
+                Multiplier::ONE,
+            ),
+            Self::EliteVoidMagic => (
+                CombatClass::Magic,
+                vec![DamageType::Magic],
+                Multiplier::new(29, 20),
+                Multiplier::new(41, 40),
+            ),
+            // Effects which can not be expressed by multipliers, Dharok's effect is applied by
+            // the `Attacker`
+            _ => (
+                CombatClass::Melee,
+                Vec::new(),
+                Multiplier::ONE,
+                Multiplier::ONE,
+            ),
+        };
+
+        Some(SetBonus {
+            set: self,
+            class,
+            damage_types,
+            accuracy,
+            damage,
+        })
+    }
+}
+
+/// Effect of an [`ArmourSet`] in a [`Loadout`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetBonus {
+    /// The set providing the effect.
+    pub set: ArmourSet,
+    /// Combat class the multipliers apply to.
+    pub class: CombatClass,
+    /// Damage types the multipliers apply to. Empty if the effect can not be expressed by
+    /// multipliers.
+    pub damage_types: Vec<DamageType>,
+    /// Multiplier for the attack roll.
+    pub accuracy: Multiplier,
+    /// Multiplier for the max hit.
+    pub damage: Multiplier,
+}
+
+impl SetBonus {
+    /// Returns `true` if the multipliers apply to attacks of `damage_type`.
+    #[must_use]
+    pub fn applies_to(&self, damage_type: DamageType) -> bool {
+        self.damage_types.contains(&damage_type)
+    }
+
+    /// Convert the multipliers to flat bonuses with the same effect on `stats`.
+    pub fn apply_to(&self, stats: &mut CombatStats) {
+        if !self.damage_types.is_empty() {
+            apply_multipliers(
+                stats,
+                self.class,
+                self.damage_types.iter().copied(),
+                self.accuracy,
+                self.damage,
+            );
+        }
+    }
+}
+
+impl Loadout<'_> {
+    /// Effects of the armour sets which are equipped completely enough.
+    #[must_use]
+    pub fn set_bonuses(&self) -> Vec<SetBonus> {
+        let mut result: Vec<_> = ArmourSet::into_enum_iter()
+            .filter_map(|set| {
+                let pieces: Vec<_> = self
+                    .iter()
+                    .filter(|i| i.armour_sets.contains(&set))
+                    .collect();
+                set.bonus(&pieces)
+            })
+            .collect();
+
+        let superseded: Vec<_> = result.iter().filter_map(|b| b.set.supersedes()).collect();
+        result.retain(|b| !superseded.contains(&b.set));
+
+        result
+    }
+
+    /// Fraction of the damage of `damage_type` taken which is prevented by set effects, between 0
+    /// and 1.
+    ///
+    /// The full [Justiciar armour](ArmourSet::Justiciar) reduces damage by its defence bonus
+    /// against the damage type divided by 3000.
+    #[must_use]
+    pub fn damage_reduction(&self, damage_type: DamageType) -> f64 {
+        let justiciar = self
+            .set_bonuses()
+            .iter()
+            .any(|b| b.set == ArmourSet::Justiciar);
+        if !justiciar {
+            return 0.0;
+        }
+
+        let defence = self.combat_stats().defence[damage_type].max(0);
+        (f64::from(defence) / 3000.0).min(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AttackStyle, Attacker, CombatOption, Context, DamageTypeStats, PlayerLevels, Skill, Target,
+    };
+
+    fn item(name: &str, equip_slot: EquipSlot, armour_sets: &[ArmourSet]) -> Item {
+        Item::test(name, equip_slot).with_sets(armour_sets)
+    }
+
+    #[test]
+    fn void() {
+        use ArmourSet::{EliteVoidRanged, VoidMagic, VoidMelee, VoidRanged};
+
+        let all = [VoidMelee, VoidRanged, VoidMagic];
+        let helm = item(
+            "Void ranger helm",
+            EquipSlot::Head,
+            &[VoidRanged, EliteVoidRanged],
+        );
+        let top = item(
+            "Elite void top",
+            EquipSlot::Body,
+            &[VoidMelee, VoidRanged, VoidMagic, EliteVoidRanged],
+        );
+        let robe = item("Void knight robe", EquipSlot::Legs, &all);
+        let elite_robe = item(
+            "Elite void robe",
+            EquipSlot::Legs,
+            &[VoidMelee, VoidRanged, VoidMagic, EliteVoidRanged],
+        );
+        let gloves = item(
+            "Void knight gloves",
+            EquipSlot::Hands,
+            &[VoidMelee, VoidRanged, VoidMagic, EliteVoidRanged],
+        );
+
+        let mut loadout = Loadout::new();
+        for i in [&helm, &top, &robe] {
+            loadout.equip(i);
+        }
+        assert!(loadout.set_bonuses().is_empty());
+
+        loadout.equip(&gloves);
+        let bonuses = loadout.set_bonuses();
+        assert_eq!(bonuses.len(), 1);
+        assert_eq!(bonuses[0].set, VoidRanged);
+
+        loadout.equip(&elite_robe);
+        let bonuses = loadout.set_bonuses();
+        assert_eq!(bonuses.len(), 1);
+        assert_eq!(bonuses[0].set, EliteVoidRanged);
+        assert!(bonuses[0].applies_to(DamageType::Ranged));
+        assert_eq!(bonuses[0].damage, Multiplier::new(9, 8));
+    }
+
+    #[test]
+    fn partial() {
+        let helm = item(
+            "Inquisitor's great helm",
+            EquipSlot::Head,
+            &[ArmourSet::Inquisitor],
+        );
+        let body = item(
+            "Inquisitor's hauberk",
+            EquipSlot::Body,
+            &[ArmourSet::Inquisitor],
+        );
+        let legs = item(
+            "Inquisitor's plateskirt",
+            EquipSlot::Legs,
+            &[ArmourSet::Inquisitor],
+        );
+
+        let mut loadout = Loadout::new();
+        loadout.equip(&helm);
+        loadout.equip(&body);
+        let bonuses = loadout.set_bonuses();
+        assert_eq!(bonuses[0].accuracy, Multiplier::new(1010, 1000));
+        assert!(bonuses[0].applies_to(DamageType::Crush));
+        assert!(!bonuses[0].applies_to(DamageType::Stab));
+
+        loadout.equip(&legs);
+        assert_eq!(loadout.set_bonuses()[0].damage, Multiplier::new(1025, 1000));
+
+        let crystal_body = item("Crystal body", EquipSlot::Body, &[ArmourSet::Crystal]);
+        let bow = item(
+            "Bow of faerdhinen",
+            EquipSlot::TwoHanded,
+            &[ArmourSet::Crystal],
+        );
+
+        let mut loadout = Loadout::new();
+        loadout.equip(&crystal_body);
+        assert!(loadout.set_bonuses().is_empty());
+
+        loadout.equip(&bow);
+        let bonuses = loadout.set_bonuses();
+        assert_eq!(bonuses[0].accuracy, Multiplier::new(1150, 1000));
+        assert_eq!(bonuses[0].damage, Multiplier::new(1075, 1000));
+    }
+
+    #[test]
+    fn dharok() {
+        let mut pieces = [
+            item("Dharok's helm", EquipSlot::Head, &[ArmourSet::Dharok]),
+            item("Dharok's platebody", EquipSlot::Body, &[ArmourSet::Dharok]),
+            item("Dharok's platelegs", EquipSlot::Legs, &[ArmourSet::Dharok]),
+            item(
+                "Dharok's greataxe",
+                EquipSlot::TwoHanded,
+                &[ArmourSet::Dharok],
+            ),
+        ];
+        pieces[3].combat_stats.melee_strength = 105;
+        let option = CombatOption {
+            name: "Smash".to_string(),
+            style: AttackStyle::Aggressive,
+            damage_type: DamageType::Crush,
+        };
+        let levels = PlayerLevels::maxed();
+
+        let mut loadout = Loadout::new();
+        for piece in &pieces {
+            loadout.equip(piece);
+        }
+        let mut attacker = Attacker::new(&levels, &loadout, &option, 0);
+        let full = attacker.against(&Target::default()).max_hit;
+        assert_eq!(full, attacker.max_hit());
+
+        // 98 missing hitpoints at level 99: +97.02%
+        attacker.current_hitpoints = 1;
+        assert_eq!(
+            attacker.dharok_multiplier(),
+            Multiplier::new(19_702, 10_000)
+        );
+        assert_eq!(
+            attacker.against(&Target::default()).max_hit,
+            full * 19_702 / 10_000
+        );
+
+        let mut levels = PlayerLevels::maxed();
+        levels.set(Skill::Hitpoints, 50);
+        loadout.unequip(EquipSlot::Head);
+        let mut attacker = Attacker::new(&levels, &loadout, &option, 0);
+        attacker.current_hitpoints = 10;
+        assert_eq!(
+            attacker.against(&Target::default()).max_hit,
+            attacker.max_hit()
+        );
+    }
+
+    #[test]
+    fn justiciar() {
+        let justiciar = |name, slot, defence| {
+            let mut item = item(name, slot, &[ArmourSet::Justiciar]);
+            item.combat_stats.defence = DamageTypeStats::new(&defence);
+            item
+        };
+        let helm = justiciar("Justiciar faceguard", EquipSlot::Head, [60, 63, 59, -6, 67]);
+        let body = justiciar(
+            "Justiciar chestguard",
+            EquipSlot::Body,
+            [132, 130, 117, -11, 142],
+        );
+        let legs = justiciar(
+            "Justiciar legguards",
+            EquipSlot::Legs,
+            [95, 92, 93, -31, 102],
+        );
+
+        let mut loadout = Loadout::new();
+        loadout.equip(&helm);
+        loadout.equip(&body);
+        assert_eq!(loadout.damage_reduction(DamageType::Stab), 0.0);
+
+        loadout.equip(&legs);
+        assert!((loadout.damage_reduction(DamageType::Stab) - 287.0 / 3000.0).abs() < 1e-9);
+        assert!((loadout.damage_reduction(DamageType::Ranged) - 311.0 / 3000.0).abs() < 1e-9);
+        assert_eq!(loadout.damage_reduction(DamageType::Magic), 0.0);
+    }
+
+    #[test]
+    fn ranking() {
+        let obsidian = |name, slot, strength| {
+            let mut item = item(name, slot, &[ArmourSet::Obsidian]);
+            item.combat_stats.melee_strength = strength;
+            item
+        };
+        let mut neitiznot = item("Helm of neitiznot", EquipSlot::Head, &[]);
+        neitiznot.combat_stats.melee_strength = 3;
+        let helmet = obsidian("Obsidian helmet", EquipSlot::Head, 0);
+        let body = obsidian("Obsidian platebody", EquipSlot::Body, 0);
+        let legs = obsidian("Obsidian platelegs", EquipSlot::Legs, 0);
+        let mace = obsidian("Tzhaar-ket-em", EquipSlot::Weapon, 40);
+
+        let loadout = Loadout::best(
+            |slot| {
+                match slot {
+                    EquipSlot::Head => vec![&neitiznot, &helmet],
+                    EquipSlot::Body => vec![&body],
+                    EquipSlot::Legs => vec![&legs],
+                    EquipSlot::Weapon => vec![&mace],
+                    _ => Vec::new(),
+                }
+                .into_iter()
+            },
+            |a, b| b.melee_strength.cmp(&a.melee_strength),
+            &Context::default(),
+        );
+
+        assert_eq!(loadout[EquipSlot::Head].unwrap().name, "Obsidian helmet");
+        assert_eq!(loadout.set_bonuses()[0].set, ArmourSet::Obsidian);
+        assert_eq!(
+            loadout.effective_stats(&Context::default()).melee_strength,
+            50
+        );
+    }
+}
@@ -0,0 +1,485 @@
+use enum_iterator::IntoEnumIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ArmourSet, AttackStyle, CombatClass, CombatOption, CombatStats, ConditionalBonus, Context,
+    DamageType, DamageTypeStats, Loadout, Multiplier, PlayerLevels, SetBonus, Skill,
+};
+
+/// Attack delay in game-ticks when fighting without a weapon.
+pub const UNARMED_ATTACK_DELAY: u8 = 4;
+
+/// Attack delay in game-ticks when casting a spell, independent of the weapon.
+pub const SPELL_ATTACK_DELAY: u8 = 5;
+
+/// Length of a game-tick in seconds.
+pub const TICK_SECONDS: f64 = 0.6;
+
+/// Offensive prayers, only the strongest prayer of each combat class is listed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, IntoEnumIterator, Deserialize, Serialize)]
+pub enum Prayer {
+    /// Ultimate Strength and Incredible Reflexes, for players without Chivalry.
+    UltimateStrength,
+    /// Chivalry.
+    Chivalry,
+    /// Piety.
+    Piety,
+    /// Eagle Eye.
+    EagleEye,
+    /// Rigour.
+    Rigour,
+    /// Mystic Might.
+    MysticMight,
+    /// Augury.
+    Augury,
+}
+
+impl Prayer {
+    /// Combat class boosted by the prayer.
+    #[must_use]
+    pub const fn class(self) -> CombatClass {
+        match self {
+            Self::UltimateStrength | Self::Chivalry | Self::Piety => CombatClass::Melee,
+            Self::EagleEye | Self::Rigour => CombatClass::Ranged,
+            Self::MysticMight | Self::Augury => CombatClass::Magic,
+        }
+    }
+
+    /// Percentage boosts to the accuracy and strength levels.
+    #[must_use]
+    pub const fn boosts(self) -> (u32, u32) {
+        match self {
+            Self::UltimateStrength | Self::EagleEye => (15, 15),
+            Self::Chivalry => (15, 18),
+            Self::Piety | Self::Rigour => (20, 23),
+            Self::MysticMight => (15, 0),
+            Self::Augury => (25, 0),
+        }
+    }
+
+    /// Human readable name.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::UltimateStrength => "Ultimate Strength",
+            Self::Chivalry => "Chivalry",
+            Self::Piety => "Piety",
+            Self::EagleEye => "Eagle Eye",
+            Self::Rigour => "Rigour",
+            Self::MysticMight => "Mystic Might",
+            Self::Augury => "Augury",
+        }
+    }
+}
+
+/// Defensive properties of an opponent.
+#[derive(Debug, Clone, Default)]
+pub struct Target {
+    /// Defence level.
+    pub defence_level: u16,
+    /// Magic level, used to defend against magic attacks.
+    pub magic_level: u16,
+    /// Defence bonuses.
+    pub defence: DamageTypeStats,
+    /// Situation of the fight, determines which [`ConditionalBonus`]es apply.
+    pub context: Context,
+}
+
+impl Target {
+    /// Defence roll against attacks of `damage_type`.
+    #[must_use]
+    pub fn defence_roll(&self, damage_type: DamageType) -> u32 {
+        let level = match damage_type {
+            DamageType::Magic => self.magic_level,
+            _ => self.defence_level,
+        };
+        roll(u32::from(level) + 9, self.defence[damage_type])
+    }
+}
+
+/// The offensive setup of a player.
+#[derive(Debug, Clone)]
+pub struct Attacker<'a> {
+    /// The player's levels.
+    pub levels: &'a PlayerLevels,
+    /// Combined equipment stats.
+    pub stats: CombatStats,
+    /// Style of the chosen combat option.
+    pub style: AttackStyle,
+    /// Damage type of the chosen combat option.
+    pub damage_type: DamageType,
+    /// Delay between attacks in game-ticks, without style modifiers.
+    pub attack_delay: u8,
+    /// Base max hit of the spell being cast. Only relevant for magic attacks.
+    pub spell_max_hit: u16,
+    /// Active offensive prayer.
+    pub prayer: Option<Prayer>,
+    /// Current hitpoints, which boost the damage of Dharok's equipment when low.
+    pub current_hitpoints: u8,
+    /// Conditional bonuses of the equipment.
+    pub bonuses: Vec<ConditionalBonus>,
+    /// Effects of equipped armour sets.
+    pub set_bonuses: Vec<SetBonus>,
+}
+
+impl<'a> Attacker<'a> {
+    /// Create an attacker using the weapon and equipment of `loadout`. Magic attacks cast a spell
+    /// with a base max hit of `spell_max_hit`.
+    #[must_use]
+    pub fn new(
+        levels: &'a PlayerLevels,
+        loadout: &Loadout,
+        option: &CombatOption,
+        spell_max_hit: u16,
+    ) -> Self {
+        let attack_delay = match option.damage_type {
+            DamageType::Magic => SPELL_ATTACK_DELAY,
+            _ => loadout
+                .weapon()
+                .and_then(|w| w.weapon_data.as_ref())
+                .map_or(UNARMED_ATTACK_DELAY, |w| w.attack_delay),
+        };
+
+        Self {
+            levels,
+            stats: loadout.combat_stats(),
+            style: option.style,
+            damage_type: option.damage_type,
+            attack_delay,
+            spell_max_hit,
+            prayer: None,
+            current_hitpoints: levels.get(Skill::Hitpoints),
+            bonuses: loadout
+                .iter()
+                .flat_map(|i| i.conditional_bonuses.iter().copied())
+                .collect(),
+            set_bonuses: loadout.set_bonuses(),
+        }
+    }
+
+    /// The skill determining accuracy and damage.
+    fn skill(&self) -> Skill {
+        match self.damage_type {
+            DamageType::Stab | DamageType::Slash | DamageType::Crush => Skill::Attack,
+            DamageType::Magic => Skill::Magic,
+            DamageType::Ranged => Skill::Ranged,
+        }
+    }
+
+    /// Effective level for accuracy, including invisible style bonuses.
+    #[must_use]
+    pub fn effective_accuracy_level(&self) -> u32 {
+        let level = u32::from(self.levels.get(self.skill()));
+        let (style_bonus, base) = match (self.damage_type, self.style) {
+            (DamageType::Magic, AttackStyle::Accurate) => (2, 9),
+            (DamageType::Magic, AttackStyle::LongRange) => (1, 9),
+            (DamageType::Magic, _) => (0, 9),
+            (_, AttackStyle::Accurate) => (3, 8),
+            (_, AttackStyle::Controlled) => (1, 8),
+            _ => (0, 8),
+        };
+        self.boost(level, true) + style_bonus + base
+    }
+
+    /// Effective level for damage, including invisible style bonuses.
+    ///
+    /// Magic damage does not depend on a level, this returns the magic level in that case.
+    #[must_use]
+    pub fn effective_strength_level(&self) -> u32 {
+        let (skill, style_bonus) = match (self.damage_type, self.style) {
+            (DamageType::Magic, _) => return u32::from(self.levels.get(Skill::Magic)),
+            (DamageType::Ranged, AttackStyle::Accurate) => (Skill::Ranged, 3),
+            (DamageType::Ranged, _) => (Skill::Ranged, 0),
+            (_, AttackStyle::Aggressive) => (Skill::Strength, 3),
+            (_, AttackStyle::Controlled) => (Skill::Strength, 1),
+            _ => (Skill::Strength, 0),
+        };
+        self.boost(u32::from(self.levels.get(skill)), false) + style_bonus + 8
+    }
+
+    /// Apply the accuracy or strength boost of the active prayer to `level`.
+    fn boost(&self, level: u32, accuracy: bool) -> u32 {
+        match self.prayer {
+            Some(prayer) if prayer.class() == self.damage_type.class() => {
+                let (accuracy_boost, strength_boost) = prayer.boosts();
+                let boost = if accuracy {
+                    accuracy_boost
+                } else {
+                    strength_boost
+                };
+                level * (100 + boost) / 100
+            }
+            _ => level,
+        }
+    }
+
+    /// Attack roll used to determine the chance to hit.
+    #[must_use]
+    pub fn attack_roll(&self) -> u32 {
+        roll(
+            self.effective_accuracy_level(),
+            self.stats.attack[self.damage_type],
+        )
+    }
+
+    /// Maximum damage of a single hit.
+    #[must_use]
+    pub fn max_hit(&self) -> u32 {
+        let strength_bonus = match self.damage_type {
+            DamageType::Magic => {
+                let bonus = u32::try_from(100 + i32::from(self.stats.magic_damage)).unwrap_or(0);
+                return u32::from(self.spell_max_hit) * bonus / 100;
+            }
+            DamageType::Ranged => self.stats.ranged_strength,
+            _ => self.stats.melee_strength,
+        };
+
+        let bonus = u32::try_from(i32::from(strength_bonus) + 64).unwrap_or(0);
+        (self.effective_strength_level() * bonus + 320) / 640
+    }
+
+    /// Damage multiplier of [Dharok's equipment](ArmourSet::Dharok): +1% per missing hitpoint,
+    /// scaled by the hitpoints level divided by 100.
+    #[must_use]
+    pub fn dharok_multiplier(&self) -> Multiplier {
+        let level = self.levels.get(Skill::Hitpoints);
+        let missing = level.saturating_sub(self.current_hitpoints);
+        Multiplier::new(10_000 + u16::from(missing) * u16::from(level), 10_000)
+    }
+
+    /// Delay between attacks in game-ticks. The rapid style attacks one tick faster.
+    #[must_use]
+    pub fn attack_delay(&self) -> u8 {
+        match self.style {
+            AttackStyle::Rapid => self.attack_delay.saturating_sub(1).max(1),
+            _ => self.attack_delay,
+        }
+    }
+
+    /// Calculate the outcome of attacking `target`, including set effects and the conditional
+    /// bonuses which apply in its [`Context`].
+    #[must_use]
+    pub fn against(&self, target: &Target) -> Damage {
+        let (attack_roll, max_hit) = self
+            .set_bonuses
+            .iter()
+            .filter(|b| b.applies_to(self.damage_type))
+            .fold((self.attack_roll(), self.max_hit()), |(roll, hit), b| {
+                (b.accuracy.apply(roll), b.damage.apply(hit))
+            });
+
+        let class = self.damage_type.class();
+        let dharok = self.set_bonuses.iter().any(|b| b.set == ArmourSet::Dharok);
+        let max_hit = if dharok && class == CombatClass::Melee {
+            self.dharok_multiplier().apply(max_hit)
+        } else {
+            max_hit
+        };
+
+        let (attack_roll, max_hit) = target
+            .context
+            .active(&self.bonuses)
+            .into_iter()
+            .filter(|b| b.class == class)
+            .fold((attack_roll, max_hit), |(roll, hit), b| {
+                (b.accuracy.apply(roll), b.damage.apply(hit))
+            });
+        let defence_roll = target.defence_roll(self.damage_type);
+
+        Damage {
+            max_hit,
+            attack_roll,
+            defence_roll,
+            hit_chance: hit_chance(attack_roll, defence_roll),
+            attack_delay: self.attack_delay(),
+        }
+    }
+}
+
+/// Outcome of an [`Attacker`] fighting a [`Target`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Damage {
+    /// Maximum damage of a single hit.
+    pub max_hit: u32,
+    /// The attacker's accuracy roll.
+    pub attack_roll: u32,
+    /// The target's defence roll.
+    pub defence_roll: u32,
+    /// Chance for an attack to hit, between 0 and 1.
+    pub hit_chance: f64,
+    /// Delay between attacks in game-ticks.
+    pub attack_delay: u8,
+}
+
+impl Damage {
+    /// Average damage per attack. Hits are uniformly distributed between 0 and the max hit.
+    #[must_use]
+    pub fn expected_hit(&self) -> f64 {
+        self.hit_chance * f64::from(self.max_hit) / 2.0
+    }
+
+    /// Expected damage per second.
+    #[must_use]
+    pub fn dps(&self) -> f64 {
+        self.expected_hit() / (f64::from(self.attack_delay) * TICK_SECONDS)
+    }
+}
+
+/// Chance for an attack to hit.
+///
+/// See [Accuracy](https://oldschool.runescape.wiki/w/Accuracy).
+#[must_use]
+pub fn hit_chance(attack_roll: u32, defence_roll: u32) -> f64 {
+    let attack = f64::from(attack_roll);
+    let defence = f64::from(defence_roll);
+
+    if attack_roll > defence_roll {
+        1.0 - (defence + 2.0) / (2.0 * (attack + 1.0))
+    } else {
+        attack / (2.0 * (defence + 1.0))
+    }
+}
+
+/// Accuracy or defence roll from an effective level and an equipment bonus.
+fn roll(effective_level: u32, bonus: i16) -> u32 {
+    effective_level * u32::try_from(i32::from(bonus) + 64).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        CombatClass, Condition, EquipSlot, Item, MonsterAttribute, Multiplier, WeaponData,
+    };
+
+    fn attacker(
+        levels: &PlayerLevels,
+        style: AttackStyle,
+        damage_type: DamageType,
+    ) -> Attacker<'_> {
+        Attacker {
+            levels,
+            stats: CombatStats::default(),
+            style,
+            damage_type,
+            attack_delay: 4,
+            spell_max_hit: 0,
+            prayer: None,
+            current_hitpoints: levels.get(Skill::Hitpoints),
+            bonuses: Vec::new(),
+            set_bonuses: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn melee() {
+        let levels = PlayerLevels::maxed();
+        let mut attacker = attacker(&levels, AttackStyle::Aggressive, DamageType::Slash);
+        assert_eq!(attacker.effective_strength_level(), 110);
+        assert_eq!(attacker.max_hit(), 11);
+
+        attacker.stats.attack[DamageType::Slash] = 82 + 67 + 8;
+        attacker.stats.melee_strength = 82 + 6 + 3 + 5 + 6 + 2 + 4;
+        assert_eq!(attacker.max_hit(), 30);
+        assert_eq!(attacker.attack_roll(), 107 * 221);
+    }
+
+    #[test]
+    fn prayer() {
+        let levels = PlayerLevels::maxed();
+        let mut attacker = attacker(&levels, AttackStyle::Aggressive, DamageType::Crush);
+        attacker.prayer = Some(Prayer::Rigour);
+        assert_eq!(attacker.effective_strength_level(), 110);
+
+        attacker.prayer = Some(Prayer::Piety);
+        assert_eq!(attacker.effective_strength_level(), 121 + 3 + 8);
+        assert_eq!(attacker.effective_accuracy_level(), 118 + 8);
+    }
+
+    #[test]
+    fn ranged() {
+        let levels = PlayerLevels::maxed();
+        let mut attacker = attacker(&levels, AttackStyle::Rapid, DamageType::Ranged);
+        attacker.stats.ranged_strength = 100;
+        attacker.attack_delay = 5;
+
+        assert_eq!(attacker.max_hit(), (107 * 164 + 320) / 640);
+        assert_eq!(attacker.attack_delay(), 4);
+    }
+
+    #[test]
+    fn magic() {
+        let levels = PlayerLevels::maxed();
+        let mut attacker = attacker(&levels, AttackStyle::AutoCast, DamageType::Magic);
+        attacker.spell_max_hit = 24;
+        attacker.stats.magic_damage = 10;
+
+        assert_eq!(attacker.max_hit(), 26);
+        assert_eq!(attacker.effective_accuracy_level(), 108);
+
+        // Spells are cast every 5 ticks, whatever the weapon
+        let mut staff = Item::test("Staff", EquipSlot::Weapon);
+        staff.weapon_data = Some(WeaponData {
+            attack_delay: 4,
+            combat_options: Vec::new(),
+        });
+        let mut loadout = Loadout::new();
+        loadout.equip(&staff);
+        let option = CombatOption {
+            name: "Spell".to_string(),
+            style: AttackStyle::AutoCast,
+            damage_type: DamageType::Magic,
+        };
+        let attacker = Attacker::new(&levels, &loadout, &option, 24);
+        assert_eq!(attacker.spell_max_hit, 24);
+        assert_eq!(attacker.attack_delay(), SPELL_ATTACK_DELAY);
+    }
+
+    #[test]
+    fn accuracy() {
+        assert!((hit_chance(100, 100) - 100.0 / 202.0).abs() < 1e-9);
+        assert!((hit_chance(201, 100) - (1.0 - 102.0 / 404.0)).abs() < 1e-9);
+        assert_eq!(hit_chance(0, 100), 0.0);
+
+        let target = Target {
+            defence_level: 100,
+            magic_level: 1,
+            defence: DamageTypeStats::new(&[10, 0, 0, -10, 0]),
+            context: Context::default(),
+        };
+        assert_eq!(target.defence_roll(DamageType::Stab), 109 * 74);
+        assert_eq!(target.defence_roll(DamageType::Magic), 10 * 54);
+    }
+
+    #[test]
+    fn conditional() {
+        let levels = PlayerLevels::maxed();
+        let mut attacker = attacker(&levels, AttackStyle::Aggressive, DamageType::Stab);
+        attacker.bonuses.push(ConditionalBonus::new(
+            Condition::Attribute(MonsterAttribute::Dragon),
+            CombatClass::Melee,
+            Multiplier::new(6, 5),
+        ));
+
+        let mut target = Target::default();
+        let plain = attacker.against(&target);
+        target.context.attributes.push(MonsterAttribute::Dragon);
+        let boosted = attacker.against(&target);
+
+        assert_eq!(boosted.attack_roll, plain.attack_roll * 6 / 5);
+        assert_eq!(boosted.max_hit, plain.max_hit * 6 / 5);
+    }
+
+    #[test]
+    fn dps() {
+        let damage = Damage {
+            max_hit: 40,
+            attack_roll: 0,
+            defence_roll: 0,
+            hit_chance: 0.5,
+            attack_delay: 4,
+        };
+        assert!((damage.expected_hit() - 10.0).abs() < 1e-9);
+        assert!((damage.dps() - 10.0 / 2.4).abs() < 1e-9);
+    }
+}
@@ -34,8 +34,38 @@ impl Display for DamageType {
     }
 }
 
+impl DamageType {
+    /// The combat class this damage type belongs to.
+    #[must_use]
+    pub fn class(self) -> CombatClass {
+        match self {
+            Self::Stab | Self::Slash | Self::Crush => CombatClass::Melee,
+            Self::Magic => CombatClass::Magic,
+            Self::Ranged => CombatClass::Ranged,
+        }
+    }
+}
+
+/// The three combat classes, grouping the melee [`DamageType`]s together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, IntoEnumIterator)]
+pub enum CombatClass {
+    /// Stab, slash and crush damage.
+    Melee,
+    /// Magic damage.
+    Magic,
+    /// Ranged damage.
+    Ranged,
+}
+
+impl CombatClass {
+    /// Iterate over the damage types of this class.
+    pub fn damage_types(self) -> impl Iterator<Item = DamageType> {
+        DamageType::into_enum_iter().filter(move |t| t.class() == self)
+    }
+}
+
 /// Contains one value for each [`DamageType`].
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct Stats([i16; DamageType::VARIANT_COUNT]);
 
 impl Stats {
@@ -1,50 +1,131 @@
-use std::{
-    cmp::Ordering,
-    iter::FromIterator,
-    ops::{Index, IndexMut},
-};
+use std::{cmp::Ordering, collections::HashMap, iter::FromIterator, ops::Index};
 
 use enum_iterator::IntoEnumIterator;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{EquipSlot, Item};
 
+type Slots = [Vec<Item>; EquipSlot::VARIANT_COUNT];
+
+/// Position of an item inside the [`Database`].
+type Position = (EquipSlot, usize);
+
 /// Contains a database of all equipment items separated by equip slots.
 ///
-/// Use [`Index<EquipSlot>`](#impl-Index<EquipSlot>) and [`IndexMut<EquipSlot>`](#impl-IndexMut<EquipSlot>)
-/// implementations to access individual slots.
-#[derive(Debug, Default, Deserialize, Serialize)]
-pub struct Database([Vec<Item>; EquipSlot::VARIANT_COUNT]);
+/// Use the [`Index<EquipSlot>`](#impl-Index<EquipSlot>) implementation to access individual
+/// slots. Items can also be looked up by id or name, see [`Database::get_by_id`],
+/// [`Database::get_by_name`] and [`Database::find_by_name`].
+#[derive(Debug, Default)]
+pub struct Database {
+    slots: Slots,
+    ids: HashMap<u32, Position>,
+    names: HashMap<String, Position>,
+    normalized_names: HashMap<String, Position>,
+}
 
 impl Database {
     /// Sort each database slot individually.
     pub fn sort<F: FnMut(&Item, &Item) -> Ordering + Copy>(&mut self, compare: F) {
-        for slot in &mut self.0 {
+        for slot in &mut self.slots {
             slot.sort_unstable_by(compare);
         }
+        self.reindex();
     }
 
     /// Get the total amount of items in the database.
     #[must_use]
     pub fn len(&self) -> usize {
-        self.0.iter().fold(0, |acc, e| acc + e.len())
+        self.slots.iter().fold(0, |acc, e| acc + e.len())
     }
 
     /// Return true if no item is stored in the database
     #[must_use]
     pub fn is_empty(&self) -> bool {
-        self.0.iter().all(Vec::is_empty)
+        self.slots.iter().all(Vec::is_empty)
+    }
+
+    /// Iterate over the items of all slots.
+    pub fn iter(&self) -> impl Iterator<Item = &Item> {
+        self.slots.iter().flatten()
+    }
+
+    /// Get an item by its OSRS id.
+    ///
+    /// Ids of items which were aggregated into another item resolve to the aggregate.
+    #[must_use]
+    pub fn get_by_id(&self, id: u32) -> Option<&Item> {
+        self.ids.get(&id).map(|&p| self.get(p))
+    }
+
+    /// Get an item by its exact name.
+    #[must_use]
+    pub fn get_by_name(&self, name: &str) -> Option<&Item> {
+        self.names.get(name).map(|&p| self.get(p))
+    }
+
+    /// Get an item by name, ignoring case, punctuation and surplus whitespace.
+    ///
+    /// See [`normalize_name`].
+    #[must_use]
+    pub fn find_by_name(&self, name: &str) -> Option<&Item> {
+        self.normalized_names
+            .get(&normalize_name(name))
+            .map(|&p| self.get(p))
+    }
+
+    fn get(&self, (slot, idx): Position) -> &Item {
+        &self[slot][idx]
+    }
+
+    /// Rebuild the lookup tables after the item order has changed.
+    fn reindex(&mut self) {
+        self.ids.clear();
+        self.names.clear();
+        self.normalized_names.clear();
+
+        for slot in EquipSlot::into_enum_iter() {
+            for (idx, item) in self.slots[slot as usize].iter().enumerate() {
+                let position = (slot, idx);
+                for &id in &item.source_ids {
+                    self.ids.entry(id).or_insert(position);
+                }
+                self.ids.insert(item.id, position);
+                self.names.entry(item.name.clone()).or_insert(position);
+                self.normalized_names
+                    .entry(normalize_name(&item.name))
+                    .or_insert(position);
+            }
+        }
     }
 }
 
+/// Normalize an item name for lookups: lowercase, without punctuation and with single spaces
+/// between words.
+///
+/// `"Rangers' tunic"` and `"rangers tunic"` both normalize to `"rangers tunic"`.
+#[must_use]
+pub fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 impl FromIterator<Item> for Database {
     fn from_iter<T: IntoIterator<Item = Item>>(iter: T) -> Self {
         let mut result = Self::default();
 
         for item in iter {
-            result[item.equip_slot].push(item);
+            result.slots[item.equip_slot as usize].push(item);
         }
 
+        result.reindex();
         result
     }
 }
@@ -53,12 +134,71 @@ impl Index<EquipSlot> for Database {
     type Output = Vec<Item>;
 
     fn index(&self, slot: EquipSlot) -> &Self::Output {
-        &self.0[slot as usize]
+        &self.slots[slot as usize]
+    }
+}
+
+// Only the items are stored, lookup tables are rebuilt when loading.
+impl Serialize for Database {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.slots.serialize(serializer)
     }
 }
 
-impl IndexMut<EquipSlot> for Database {
-    fn index_mut(&mut self, slot: EquipSlot) -> &mut Self::Output {
-        &mut self.0[slot as usize]
+impl<'de> Deserialize<'de> for Database {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut result = Self {
+            slots: Slots::deserialize(deserializer)?,
+            ..Self::default()
+        };
+        result.reindex();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn item(id: u32, source_ids: &[u32], name: &str, equip_slot: EquipSlot) -> Item {
+        Item::test(name, equip_slot)
+            .with_id(id)
+            .with_source_ids(source_ids)
+    }
+
+    #[test]
+    fn normalization() {
+        assert_eq!(normalize_name("Rangers' tunic"), "rangers tunic");
+        assert_eq!(
+            normalize_name("  Cape of accomplishment (t) "),
+            "cape of accomplishment t"
+        );
+        assert_eq!(normalize_name("Dragon  scimitar"), "dragon scimitar");
+    }
+
+    #[test]
+    fn lookup() {
+        let mut db: Database = [
+            item(1, &[1], "Abyssal whip", EquipSlot::Weapon),
+            item(2, &[2, 3, 4], "God full helm", EquipSlot::Head),
+            item(5, &[5], "Rangers' tunic", EquipSlot::Body),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(db.get_by_id(1).unwrap().name, "Abyssal whip");
+        assert_eq!(db.get_by_id(3).unwrap().name, "God full helm");
+        assert!(db.get_by_id(6).is_none());
+
+        assert_eq!(db.get_by_name("Rangers' tunic").unwrap().id, 5);
+        assert!(db.get_by_name("rangers tunic").is_none());
+        assert_eq!(db.find_by_name("rangers tunic").unwrap().id, 5);
+
+        db.sort(|a, b| b.name.cmp(&a.name));
+        assert_eq!(db.get_by_id(4).unwrap().name, "God full helm");
+
+        let db: Database = bincode::deserialize(&bincode::serialize(&db).unwrap()).unwrap();
+        assert_eq!(db.get_by_id(1).unwrap().name, "Abyssal whip");
     }
 }
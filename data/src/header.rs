@@ -0,0 +1,256 @@
+use std::fmt::Display;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use crate::{Database, MonsterDatabase};
+
+/// Identifies item data files.
+pub const MAGIC: [u8; 4] = *b"OSET";
+
+/// Identifies monster data files.
+pub const MONSTER_MAGIC: [u8; 4] = *b"OSEM";
+
+/// Version of the data format.
+///
+/// Has to be incremented whenever the serialized representation of [`Database`] or
+/// [`MonsterDatabase`] changes.
+pub const SCHEMA_VERSION: u32 = 7;
+
+/// Cargo features which change the serialized representation of [`Database`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Features {
+    /// The `trailblazer` feature.
+    pub trailblazer: bool,
+}
+
+impl Features {
+    /// Features this crate was compiled with.
+    #[must_use]
+    pub fn current() -> Self {
+        Self {
+            trailblazer: cfg!(feature = "trailblazer"),
+        }
+    }
+}
+
+impl Display for Features {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.trailblazer {
+            write!(f, "trailblazer")
+        } else {
+            write!(f, "default")
+        }
+    }
+}
+
+/// Header preceding the [`Database`] in an item data file or the [`MonsterDatabase`] in a monster
+/// data file.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Header {
+    /// [`MAGIC`] or [`MONSTER_MAGIC`], depending on the content.
+    pub magic: [u8; 4],
+    /// The [`SCHEMA_VERSION`] used to write the file.
+    pub schema_version: u32,
+    /// Time of creation in seconds since the Unix epoch.
+    pub build_timestamp: u64,
+    /// Features used to write the file.
+    pub features: Features,
+    /// Hash of the serialized content, see [`content_hash`].
+    pub content_hash: u64,
+}
+
+/// Errors when reading a data file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The data does not start with the expected magic bytes.
+    NotADataFile,
+    /// The data was written with a different [`SCHEMA_VERSION`].
+    SchemaMismatch {
+        /// Version of the data.
+        found: u32,
+    },
+    /// The data was written with a different feature set.
+    FeatureMismatch {
+        /// Features of the data.
+        found: Features,
+    },
+    /// The content does not match the hash in the header.
+    HashMismatch,
+    /// The data could not be deserialized.
+    Malformed(String),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotADataFile => write!(f, "not a data file"),
+            Self::SchemaMismatch { found } => write!(
+                f,
+                "data has schema version {found}, expected {SCHEMA_VERSION}"
+            ),
+            Self::FeatureMismatch { found } => write!(
+                f,
+                "data was built for {found} features, expected {}",
+                Features::current()
+            ),
+            Self::HashMismatch => write!(f, "data is corrupted"),
+            Self::Malformed(e) => write!(f, "data is malformed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl Database {
+    /// Serialize the database with a leading [`Header`].
+    ///
+    /// # Panics
+    ///
+    /// If serialization fails.
+    #[must_use]
+    pub fn encode(&self, build_timestamp: u64) -> Vec<u8> {
+        encode(MAGIC, self, build_timestamp)
+    }
+
+    /// Deserialize data created by [`Database::encode`] after validating the [`Header`].
+    ///
+    /// # Errors
+    ///
+    /// When the header does not match the current format or the content is invalid.
+    pub fn decode(bytes: &[u8]) -> Result<(Header, Self), DecodeError> {
+        decode(MAGIC, bytes)
+    }
+}
+
+impl MonsterDatabase {
+    /// Serialize the database with a leading [`Header`].
+    ///
+    /// # Panics
+    ///
+    /// If serialization fails.
+    #[must_use]
+    pub fn encode(&self, build_timestamp: u64) -> Vec<u8> {
+        encode(MONSTER_MAGIC, self, build_timestamp)
+    }
+
+    /// Deserialize data created by [`MonsterDatabase::encode`] after validating the [`Header`].
+    ///
+    /// # Errors
+    ///
+    /// When the header does not match the current format or the content is invalid.
+    pub fn decode(bytes: &[u8]) -> Result<(Header, Self), DecodeError> {
+        decode(MONSTER_MAGIC, bytes)
+    }
+}
+
+fn encode<T: Serialize>(magic: [u8; 4], content: &T, build_timestamp: u64) -> Vec<u8> {
+    let content = bincode::serialize(content).unwrap();
+    let header = Header {
+        magic,
+        schema_version: SCHEMA_VERSION,
+        build_timestamp,
+        features: Features::current(),
+        content_hash: content_hash(&content),
+    };
+
+    let mut result = bincode::serialize(&header).unwrap();
+    result.extend(content);
+    result
+}
+
+fn decode<T: DeserializeOwned>(magic: [u8; 4], bytes: &[u8]) -> Result<(Header, T), DecodeError> {
+    // Check magic and version first, the rest of the header might differ between versions.
+    let (found_magic, schema_version): ([u8; 4], u32) =
+        bincode::deserialize(bytes).map_err(|_| DecodeError::NotADataFile)?;
+    if found_magic != magic {
+        return Err(DecodeError::NotADataFile);
+    }
+    if schema_version != SCHEMA_VERSION {
+        return Err(DecodeError::SchemaMismatch {
+            found: schema_version,
+        });
+    }
+
+    let header: Header =
+        bincode::deserialize(bytes).map_err(|e| DecodeError::Malformed(e.to_string()))?;
+    if header.features != Features::current() {
+        return Err(DecodeError::FeatureMismatch {
+            found: header.features,
+        });
+    }
+
+    // Header size is constant for a given version
+    let content = &bytes[usize::try_from(bincode::serialized_size(&header).unwrap()).unwrap()..];
+    if content_hash(content) != header.content_hash {
+        return Err(DecodeError::HashMismatch);
+    }
+
+    let content =
+        bincode::deserialize(content).map_err(|e| DecodeError::Malformed(e.to_string()))?;
+    Ok((header, content))
+}
+
+/// Calculate the [FNV-1a](https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function)
+/// hash of `bytes`.
+///
+/// Unlike [`std::hash::Hash`] this is stable across platforms and compiler versions.
+#[must_use]
+pub fn content_hash(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        let bytes = Database::default().encode(1234);
+        let (header, database) = Database::decode(&bytes).unwrap();
+        assert_eq!(header.build_timestamp, 1234);
+        assert_eq!(header.features, Features::current());
+        assert!(database.is_empty());
+    }
+
+    #[test]
+    fn mismatch() {
+        let bytes = Database::default().encode(0);
+
+        assert_eq!(
+            Database::decode(&bytes[1..]).unwrap_err(),
+            DecodeError::NotADataFile
+        );
+
+        let mut other_version = bytes.clone();
+        other_version[4] += 1;
+        assert_eq!(
+            Database::decode(&other_version).unwrap_err(),
+            DecodeError::SchemaMismatch {
+                found: SCHEMA_VERSION + 1
+            }
+        );
+
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            Database::decode(&corrupted).unwrap_err(),
+            DecodeError::HashMismatch
+        );
+
+        assert_eq!(
+            MonsterDatabase::decode(&bytes).unwrap_err(),
+            DecodeError::NotADataFile
+        );
+    }
+
+    #[test]
+    fn fnv() {
+        assert_eq!(content_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(content_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
@@ -1,20 +1,32 @@
 use serde::{Deserialize, Serialize};
 
+use crate::ArmourSet;
+
 pub use attainability::*;
 pub use combat_stats::*;
+pub use conditional::*;
 pub use equip_slot::*;
+pub use price::*;
 pub use requirement::{Type as RequirementType, *};
 pub use weapon_data::*;
 
 mod attainability;
 mod combat_stats;
+mod conditional;
 mod equip_slot;
+mod price;
 mod requirement;
 mod weapon_data;
 
 /// Main item data struct.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Item {
+    /// The item's OSRS id.
+    pub id: u32,
+    /// The OSRS ids of all items represented by this item, including [`id`](Self::id).
+    ///
+    /// Contains more than one entry if similar items were aggregated into this one.
+    pub source_ids: Vec<u32>,
     /// The item's in-game name.
     pub name: String,
     /// The item's wiki URL.
@@ -27,6 +39,10 @@ pub struct Item {
     pub weight: f32,
     /// The item's combat stats.
     pub combat_stats: CombatStats,
+    /// Bonuses which only apply in certain situations.
+    pub conditional_bonuses: Vec<ConditionalBonus>,
+    /// Armour sets this item is a piece of.
+    pub armour_sets: Vec<ArmourSet>,
     /// Additional data for weapons.
     pub weapon_data: Option<WeaponData>,
     /// Equipment slot used by the item.
@@ -35,4 +51,66 @@ pub struct Item {
     pub requirements: Vec<Requirement>,
     /// Data about how to obtain the item.
     pub attainability: Attainability,
+    /// The item's monetary values.
+    pub price: Price,
+}
+
+#[cfg(test)]
+impl Item {
+    /// Members item with id `0`, without stats, requirements or price, which is tradeable. The
+    /// other fields are changed by the `with_*` methods.
+    pub(crate) fn test(name: &str, equip_slot: EquipSlot) -> Self {
+        Self {
+            id: 0,
+            source_ids: Vec::new(),
+            name: name.to_string(),
+            wiki_url: String::new(),
+            icon_data: Vec::new(),
+            members: true,
+            weight: 0.0,
+            combat_stats: CombatStats::default(),
+            conditional_bonuses: Vec::new(),
+            armour_sets: Vec::new(),
+            weapon_data: None,
+            equip_slot,
+            requirements: Vec::new(),
+            attainability: Attainability::new(true),
+            price: Price::default(),
+        }
+    }
+
+    pub(crate) fn with_id(mut self, id: u32) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub(crate) fn with_source_ids(mut self, source_ids: &[u32]) -> Self {
+        self.source_ids = source_ids.to_vec();
+        self
+    }
+
+    pub(crate) fn with_weight(mut self, weight: f32) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    pub(crate) fn with_stats(mut self, f: impl FnOnce(&mut CombatStats)) -> Self {
+        f(&mut self.combat_stats);
+        self
+    }
+
+    pub(crate) fn with_sets(mut self, armour_sets: &[ArmourSet]) -> Self {
+        self.armour_sets = armour_sets.to_vec();
+        self
+    }
+
+    pub(crate) fn with_tradeable(mut self, tradeable: bool) -> Self {
+        self.attainability = Attainability::new(tradeable);
+        self
+    }
+
+    pub(crate) fn with_price(mut self, price: Price) -> Self {
+        self.price = price;
+        self
+    }
 }
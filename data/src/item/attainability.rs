@@ -1,7 +1,11 @@
 #[cfg(feature = "trailblazer")]
 use regions::{bool_expr::BoolExpr, vars::Region};
+use std::fmt::{self, Display};
+
 use serde::{Deserialize, Serialize};
 
+use crate::{PlayerLevels, Requirement, Skill, Unlocks};
+
 /// Denotes the available clue tiers.
 #[derive(Debug, Clone, Copy, PartialOrd, Ord, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Clue {
@@ -28,6 +32,133 @@ pub enum Clue {
     },
 }
 
+impl Clue {
+    /// All clue tiers, from lowest to highest.
+    pub const TIERS: [Self; 6] = [
+        Self::Beginner,
+        Self::Easy,
+        Self::Medium,
+        Self::Hard { mega_rare: false },
+        Self::Elite { mega_rare: false },
+        Self::Master { mega_rare: false },
+    ];
+
+    /// The clue tier without the mega-rare flag, for comparing tiers.
+    #[must_use]
+    pub const fn tier(self) -> Self {
+        match self {
+            Self::Hard { .. } => Self::Hard { mega_rare: false },
+            Self::Elite { .. } => Self::Elite { mega_rare: false },
+            Self::Master { .. } => Self::Master { mega_rare: false },
+            tier => tier,
+        }
+    }
+
+    /// Returns `true` if the item is in the mega-rare part of the drop-table.
+    #[must_use]
+    pub const fn is_mega_rare(self) -> bool {
+        match self {
+            Self::Hard { mega_rare } | Self::Elite { mega_rare } | Self::Master { mega_rare } => {
+                mega_rare
+            }
+            _ => false,
+        }
+    }
+
+    /// Human readable name of the tier.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Beginner => "Beginner",
+            Self::Easy => "Easy",
+            Self::Medium => "Medium",
+            Self::Hard { .. } => "Hard",
+            Self::Elite { .. } => "Elite",
+            Self::Master { .. } => "Master",
+        }
+    }
+}
+
+/// A way to obtain an item without trading, e.g. for iron man accounts.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub enum Source {
+    /// Bought from a shop.
+    Shop {
+        /// Name of the shop.
+        name: String,
+        /// Where the shop is located.
+        location: String,
+        /// Requirements to access the shop.
+        #[serde(default)]
+        requirements: Vec<Requirement>,
+    },
+    /// Created with skills.
+    Recipe {
+        /// Levels required to create the item, including all intermediate steps.
+        levels: Vec<(Skill, u8)>,
+    },
+    /// Dropped by a monster.
+    Drop {
+        /// Name of the monster.
+        monster: String,
+        /// Average number of kills per drop, i.e. the drop rate is `1 / rate`.
+        rate: u32,
+        /// Requirements to kill the monster, e.g. a Slayer level.
+        #[serde(default)]
+        requirements: Vec<Requirement>,
+    },
+    /// Reward from a minigame.
+    Minigame {
+        /// Name of the minigame.
+        name: String,
+        /// Requirements to access the minigame.
+        #[serde(default)]
+        requirements: Vec<Requirement>,
+    },
+    /// Reward from a quest.
+    Quest {
+        /// Name of the quest.
+        name: String,
+    },
+}
+
+impl Source {
+    /// Returns `true` if a player with `levels` and `unlocks` can use this source.
+    #[must_use]
+    pub fn available(&self, levels: &PlayerLevels, unlocks: &Unlocks) -> bool {
+        match self {
+            Self::Recipe { levels: required } => required
+                .iter()
+                .all(|&(skill, level)| levels.get(skill) >= level),
+            Self::Quest { name } => unlocks.quest(name),
+            Self::Shop { requirements, .. }
+            | Self::Drop { requirements, .. }
+            | Self::Minigame { requirements, .. } => {
+                requirements.iter().all(|r| r.is_met(levels, unlocks))
+            }
+        }
+    }
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Shop { name, location, .. } => write!(f, "Shop: {} ({})", name, location),
+            Self::Recipe { levels } => {
+                write!(f, "Recipe:")?;
+                for (idx, (skill, level)) in levels.iter().enumerate() {
+                    let separator = if idx == 0 { " " } else { ", " };
+                    write!(f, "{}{} {:?}", separator, level, skill)?;
+                }
+                Ok(())
+            }
+            Self::Drop { monster, rate, .. } => write!(f, "Drop: {} (1/{})", monster, rate),
+            Self::Minigame { name, .. } => write!(f, "Minigame: {}", name),
+            Self::Quest { name } => write!(f, "Quest: {}", name),
+        }
+    }
+}
+
 /// Data about the attainability of an item. Useful for filtering.
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Attainability {
@@ -35,6 +166,8 @@ pub struct Attainability {
     pub tradeable: bool,
     /// The lowest clue tier required to obtain this item.
     pub clue: Option<Clue>,
+    /// Known ways to obtain this item without trading, `None` if they are unknown.
+    pub sources: Option<Vec<Source>>,
     /// Trailblazer requirements.
     #[cfg(feature = "trailblazer")]
     pub trailblazer: Option<BoolExpr<Region>>,
@@ -47,6 +180,92 @@ impl Attainability {
         Self {
             tradeable,
             clue: None,
+            sources: None,
         }
     }
+
+    /// Returns `true` if an iron man account with `levels` and `unlocks` can obtain this item.
+    ///
+    /// Items need an available [`Source`], whether they are tradeable or clue rewards. Items with
+    /// unknown sources are assumed to be obtainable.
+    #[must_use]
+    pub fn ironman(&self, levels: &PlayerLevels, unlocks: &Unlocks) -> bool {
+        self.sources.as_ref().is_none_or(|sources| {
+            sources
+                .iter()
+                .any(|source| source.available(levels, unlocks))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RequirementType as Type;
+
+    #[test]
+    fn tiers() {
+        let mega_rare = Clue::Elite { mega_rare: true };
+        assert!(mega_rare.is_mega_rare());
+        assert_eq!(mega_rare.tier(), Clue::TIERS[4]);
+        assert!(mega_rare.tier() <= Clue::TIERS[4]);
+        assert!(mega_rare > Clue::TIERS[4]);
+        assert!(!Clue::Medium.is_mega_rare());
+        assert!(Clue::TIERS.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn ironman() {
+        let mut levels = PlayerLevels::default();
+        let mut unlocks = Unlocks::default();
+        let mut attainability = Attainability::new(true);
+        assert!(attainability.ironman(&levels, &unlocks));
+
+        attainability.sources = Some(vec![Source::Recipe {
+            levels: vec![(Skill::Smithing, 99)],
+        }]);
+        assert!(!attainability.ironman(&levels, &unlocks));
+        levels.set(Skill::Smithing, 99);
+        assert!(attainability.ironman(&levels, &unlocks));
+        assert_eq!(
+            attainability.sources.as_ref().unwrap()[0].to_string(),
+            "Recipe: 99 Smithing"
+        );
+
+        attainability.sources = Some(vec![
+            Source::Quest {
+                name: "Monkey Madness I".to_string(),
+            },
+            Source::Drop {
+                monster: "Abyssal demon".to_string(),
+                rate: 512,
+                requirements: vec![Requirement {
+                    requirement: Type::Skill(Skill::Slayer),
+                    level: 85,
+                }],
+            },
+        ]);
+        assert!(!attainability.ironman(&levels, &unlocks));
+        levels.set(Skill::Slayer, 85);
+        assert!(attainability.ironman(&levels, &unlocks));
+        levels.set(Skill::Slayer, 1);
+        unlocks.set_quest("Monkey Madness I", true);
+        assert!(attainability.ironman(&levels, &unlocks));
+
+        // Untradeable items and clue rewards are checked as well
+        let mut gloves = Attainability::new(false);
+        assert!(gloves.ironman(&levels, &Unlocks::default()));
+        gloves.sources = Some(vec![Source::Quest {
+            name: "Recipe for Disaster".to_string(),
+        }]);
+        assert!(!gloves.ironman(&levels, &Unlocks::default()));
+        unlocks.set_quest("Recipe for Disaster", true);
+        assert!(gloves.ironman(&levels, &unlocks));
+
+        let mut reward = Attainability::new(true);
+        reward.clue = Some(Clue::Easy);
+        assert!(reward.ironman(&levels, &unlocks));
+        reward.sources = Some(Vec::new());
+        assert!(!reward.ironman(&levels, &unlocks));
+    }
 }
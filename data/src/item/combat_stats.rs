@@ -7,7 +7,7 @@ use crate::DamageTypeStats;
 /// Equipment stats of an [`Item`](crate::Item).
 ///
 /// Most values are of type [`i16`] to allow representing aggregate values for full equipment sets.
-#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
 pub struct CombatStats {
     /// Offensive stats.
     pub attack: DamageTypeStats,
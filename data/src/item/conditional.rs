@@ -0,0 +1,239 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{CombatClass, CombatStats, DamageType, Item, MonsterAttribute};
+
+/// Situation required for a [`ConditionalBonus`] to apply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Condition {
+    /// The target has the [`MonsterAttribute`].
+    Attribute(MonsterAttribute),
+    /// The target is the player's current slayer task.
+    SlayerTask,
+    /// The fight takes place in the wilderness.
+    Wilderness,
+}
+
+/// A fraction applied to accuracy rolls or max hits, rounding down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Multiplier {
+    /// Numerator of the fraction.
+    pub numerator: u16,
+    /// Denominator of the fraction.
+    pub denominator: u16,
+}
+
+impl Multiplier {
+    /// A multiplier without effect.
+    pub const ONE: Self = Self::new(1, 1);
+
+    /// Create the multiplier `numerator / denominator`.
+    #[must_use]
+    pub const fn new(numerator: u16, denominator: u16) -> Self {
+        Self {
+            numerator,
+            denominator,
+        }
+    }
+
+    /// Multiply `value`, rounding down.
+    #[must_use]
+    pub fn apply(self, value: u32) -> u32 {
+        value * u32::from(self.numerator) / u32::from(self.denominator)
+    }
+
+    /// Convert a bonus to the flat bonus with the same effect, given that the bonus is added to
+    /// `offset` before being multiplied.
+    fn apply_to_bonus(self, bonus: i16, offset: i32) -> i16 {
+        let value = (i32::from(bonus) + offset) * i32::from(self.numerator)
+            / i32::from(self.denominator)
+            - offset;
+        i16::try_from(value).unwrap_or(i16::MAX)
+    }
+}
+
+/// Bonus of an item which only applies in certain situations, e.g. against specific monsters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ConditionalBonus {
+    /// Situation in which the bonus applies.
+    pub condition: Condition,
+    /// Combat class the bonus applies to.
+    pub class: CombatClass,
+    /// Multiplier for the attack roll.
+    pub accuracy: Multiplier,
+    /// Multiplier for the max hit.
+    pub damage: Multiplier,
+}
+
+impl ConditionalBonus {
+    /// Create a bonus with the same multiplier for accuracy and damage.
+    #[must_use]
+    pub const fn new(condition: Condition, class: CombatClass, multiplier: Multiplier) -> Self {
+        Self {
+            condition,
+            class,
+            accuracy: multiplier,
+            damage: multiplier,
+        }
+    }
+}
+
+/// The situation of a fight, used to determine which [`ConditionalBonus`]es apply.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Context {
+    /// Attributes of the target.
+    pub attributes: Vec<MonsterAttribute>,
+    /// The target is the player's current slayer task.
+    pub slayer_task: bool,
+    /// The fight takes place in the wilderness.
+    pub wilderness: bool,
+}
+
+impl Context {
+    /// Returns `true` if `condition` is fulfilled.
+    #[must_use]
+    pub fn is_met(&self, condition: Condition) -> bool {
+        match condition {
+            Condition::Attribute(attribute) => self.attributes.contains(&attribute),
+            Condition::SlayerTask => self.slayer_task,
+            Condition::Wilderness => self.wilderness,
+        }
+    }
+
+    /// Select the bonuses which apply in this context.
+    ///
+    /// The bonuses of the salve amulet and the slayer helmet do not stack, so bonuses for
+    /// [`Condition::SlayerTask`] are dropped when a bonus against undead applies to the same
+    /// combat class.
+    pub fn active<'a>(
+        &self,
+        bonuses: impl IntoIterator<Item = &'a ConditionalBonus>,
+    ) -> Vec<&'a ConditionalBonus> {
+        let mut result: Vec<_> = bonuses
+            .into_iter()
+            .filter(|b| self.is_met(b.condition))
+            .collect();
+
+        let undead = Condition::Attribute(MonsterAttribute::Undead);
+        let undead_classes: Vec<_> = result
+            .iter()
+            .filter(|b| b.condition == undead)
+            .map(|b| b.class)
+            .collect();
+        result
+            .retain(|b| b.condition != Condition::SlayerTask || !undead_classes.contains(&b.class));
+
+        result
+    }
+}
+
+impl Item {
+    /// Stats including the [`ConditionalBonus`]es which apply in `context`.
+    ///
+    /// Multipliers are converted to flat bonuses with the same effect on the item's own bonuses,
+    /// which allows comparing items with and without conditional bonuses.
+    #[must_use]
+    pub fn effective_stats(&self, context: &Context) -> CombatStats {
+        let mut stats = self.combat_stats.clone();
+
+        for bonus in context.active(&self.conditional_bonuses) {
+            apply_multipliers(
+                &mut stats,
+                bonus.class,
+                bonus.class.damage_types(),
+                bonus.accuracy,
+                bonus.damage,
+            );
+        }
+
+        stats
+    }
+}
+
+/// Convert `accuracy` and `damage` multipliers for attacks of `damage_types` to flat bonuses
+/// with the same effect on `stats`.
+pub(crate) fn apply_multipliers(
+    stats: &mut CombatStats,
+    class: CombatClass,
+    damage_types: impl Iterator<Item = DamageType>,
+    accuracy: Multiplier,
+    damage: Multiplier,
+) {
+    for damage_type in damage_types {
+        let attack = &mut stats.attack[damage_type];
+        *attack = accuracy.apply_to_bonus(*attack, 64);
+    }
+
+    match class {
+        CombatClass::Melee => {
+            stats.melee_strength = damage.apply_to_bonus(stats.melee_strength, 64);
+        }
+        CombatClass::Ranged => {
+            stats.ranged_strength = damage.apply_to_bonus(stats.ranged_strength, 64);
+        }
+        CombatClass::Magic => {
+            stats.magic_damage = damage.apply_to_bonus(stats.magic_damage, 100);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNDEAD: Condition = Condition::Attribute(MonsterAttribute::Undead);
+
+    #[test]
+    fn stacking() {
+        let salve = ConditionalBonus::new(UNDEAD, CombatClass::Melee, Multiplier::new(6, 5));
+        let helmet = ConditionalBonus::new(
+            Condition::SlayerTask,
+            CombatClass::Melee,
+            Multiplier::new(7, 6),
+        );
+        let bonuses = [salve, helmet];
+
+        let mut context = Context {
+            slayer_task: true,
+            ..Context::default()
+        };
+        assert_eq!(context.active(&bonuses), [&helmet]);
+
+        context.attributes.push(MonsterAttribute::Undead);
+        assert_eq!(context.active(&bonuses), [&salve]);
+
+        context.slayer_task = false;
+        context.attributes.clear();
+        assert!(context.active(&bonuses).is_empty());
+    }
+
+    #[test]
+    fn stacking_by_class() {
+        // Salve amulet: melee only, Slayer helmet (i): all classes
+        let salve = ConditionalBonus::new(UNDEAD, CombatClass::Melee, Multiplier::new(7, 6));
+        let helmet =
+            |class| ConditionalBonus::new(Condition::SlayerTask, class, Multiplier::new(23, 20));
+        let bonuses = [
+            salve,
+            helmet(CombatClass::Melee),
+            helmet(CombatClass::Ranged),
+            helmet(CombatClass::Magic),
+        ];
+
+        let context = Context {
+            attributes: vec![MonsterAttribute::Undead],
+            slayer_task: true,
+            wilderness: false,
+        };
+        assert_eq!(context.active(&bonuses), [&salve, &bonuses[2], &bonuses[3]]);
+    }
+
+    #[test]
+    fn multipliers() {
+        assert_eq!(Multiplier::new(6, 5).apply(41), 49);
+        assert_eq!(Multiplier::ONE.apply(41), 41);
+
+        assert_eq!(Multiplier::new(7, 6).apply_to_bonus(0, 64), 10);
+        assert_eq!(Multiplier::new(6, 5).apply_to_bonus(15, 100), 38);
+        assert_eq!(Multiplier::ONE.apply_to_bonus(-20, 64), -20);
+    }
+}
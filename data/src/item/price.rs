@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+/// Monetary values of an item in coins.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Price {
+    /// The store price.
+    pub store: u32,
+    /// The high alchemy value.
+    pub high_alch: Option<u32>,
+    /// The Grand Exchange buy limit.
+    pub buy_limit: Option<u32>,
+    /// The Grand Exchange price, `None` if the item is not traded or no price data is available.
+    pub market: Option<u32>,
+    /// Coins needed to reclaim or repair the untradeable item after losing it in the wilderness.
+    pub reclaim: Option<u32>,
+}
+
+impl Price {
+    /// Value used to determine the items kept on death: the Grand Exchange price, the reclaim
+    /// cost or the store price, whichever is known first.
+    #[must_use]
+    pub fn death_value(&self) -> u32 {
+        self.market.or(self.reclaim).unwrap_or(self.store)
+    }
+}
@@ -1,7 +1,8 @@
+use enum_iterator::IntoEnumIterator;
 use serde::{Deserialize, Serialize};
 
 /// Enumeration of all skills in Old-school RuneScape.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, IntoEnumIterator)]
 #[serde(rename_all = "lowercase")]
 pub enum Skill {
     /// The Attack skill.
@@ -52,20 +53,66 @@ pub enum Skill {
     Hunter,
 }
 
-/// Requirement in skill or combat level.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+/// Requirement in skill or combat level, or an unlock like a quest.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub enum Type {
     /// A skill requirement.
     Skill(Skill),
     /// A combat level requirement.
     CombatLevel,
+    /// Completion of the named quest. The level is unused.
+    Quest(String),
+    /// Completion of the achievement diary of the named region. The level is the tier, see
+    /// [`Requirement::DIARY_TIERS`].
+    Diary(String),
+    /// Completion of a combat achievement tier. The level is the tier, see
+    /// [`Requirement::COMBAT_ACHIEVEMENT_TIERS`].
+    CombatAchievements,
 }
 
-/// A level requirement on a single skill.
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+/// A level requirement on a single skill, or another type of requirement.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Requirement {
     /// The type of requirement.
     pub requirement: Type,
     /// Required level.
     pub level: u8,
 }
+
+impl Requirement {
+    /// Names of the achievement diary tiers, the tier `n` is at index `n - 1`.
+    pub const DIARY_TIERS: [&'static str; 4] = ["Easy", "Medium", "Hard", "Elite"];
+
+    /// Names of the combat achievement tiers, the tier `n` is at index `n - 1`.
+    pub const COMBAT_ACHIEVEMENT_TIERS: [&'static str; 6] =
+        ["Easy", "Medium", "Hard", "Elite", "Master", "Grandmaster"];
+
+    /// Human readable description of what is required.
+    #[must_use]
+    pub fn name(&self) -> String {
+        match &self.requirement {
+            Type::Skill(skill) => format!("{:?}", skill),
+            Type::CombatLevel => "Combat".to_string(),
+            Type::Quest(quest) => format!("Quest: {}", quest),
+            Type::Diary(region) => format!("{} Diary", region),
+            Type::CombatAchievements => "Combat Achievements".to_string(),
+        }
+    }
+
+    /// Human readable description of the required level, empty for quests.
+    #[must_use]
+    pub fn level_name(&self) -> String {
+        let tier = |tiers: &[&str]| {
+            tiers
+                .get(usize::from(self.level).wrapping_sub(1))
+                .map_or_else(|| self.level.to_string(), ToString::to_string)
+        };
+
+        match self.requirement {
+            Type::Skill(_) | Type::CombatLevel => self.level.to_string(),
+            Type::Quest(_) => String::new(),
+            Type::Diary(_) => tier(&Self::DIARY_TIERS),
+            Type::CombatAchievements => tier(&Self::COMBAT_ACHIEVEMENT_TIERS),
+        }
+    }
+}
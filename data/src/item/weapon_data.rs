@@ -5,7 +5,7 @@ use crate::DamageType;
 /// The attack style of a [combat option](https://oldschool.runescape.wiki/w/Combat_Options).
 ///
 /// Attack styles determine stat bonuses and experience rewards.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 pub enum AttackStyle {
     /// Accurate attack style. Applies for melee, ranged and magic.
     Accurate,
@@ -2,10 +2,28 @@
 
 //! Data formats used by [osrs-equipment-tools](../osrs_equipment_tools/index.html).
 
+pub use armour_set::*;
+pub use combat::*;
 pub use damage_type::{Stats as DamageTypeStats, *};
 pub use database::*;
+pub use header::*;
 pub use item::*;
+pub use loadout::*;
+pub use manifest::*;
+pub use monster::*;
+pub use optimizer::*;
+pub use player::*;
+pub use risk::*;
 
+mod armour_set;
+mod combat;
 mod damage_type;
 mod database;
+mod header;
 mod item;
+mod loadout;
+mod manifest;
+mod monster;
+mod optimizer;
+mod player;
+mod risk;
@@ -0,0 +1,406 @@
+use std::{cmp::Ordering, ops::Index};
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::{ArmourSet, CombatStats, Context, EquipSlot, Item, Requirement};
+
+/// A set of equipped items with at most one item per equipment slot.
+///
+/// [Two handed](EquipSlot::TwoHanded) items occupy the weapon slot and prevent a shield from being
+/// equipped. Indexing with [`EquipSlot::TwoHanded`] returns the weapon if it is two handed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Loadout<'a>([Option<&'a Item>; EquipSlot::VARIANT_COUNT]);
+
+impl<'a> Loadout<'a> {
+    /// Create an empty loadout.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a loadout from the best item for each slot.
+    ///
+    /// `items` yields the candidates for a slot, the best first. Like with [`slice::sort_by`],
+    /// `compare` is expected to order better stats first. Stats include the bonuses which apply
+    /// in `context`, see [`Loadout::effective_stats`].
+    ///
+    /// A two handed item is chosen over the combination of weapon and shield, if it makes the
+    /// loadout better. Afterwards each [`ArmourSet`] is tried by replacing items with the best
+    /// pieces of the set, which is kept if the set effect makes up for the replaced items.
+    pub fn best<I: Iterator<Item = &'a Item>>(
+        items: impl Fn(EquipSlot) -> I,
+        compare: impl Fn(&CombatStats, &CombatStats) -> Ordering,
+        context: &Context,
+    ) -> Self {
+        let better = |candidate: &Self, current: &Self| {
+            compare(
+                &candidate.effective_stats(context),
+                &current.effective_stats(context),
+            ) == Ordering::Less
+        };
+
+        let mut result = Self::new();
+        for slot in EquipSlot::into_enum_iter().filter(|&s| s != EquipSlot::TwoHanded) {
+            if let Some(item) = items(slot).next() {
+                result.equip(item);
+            }
+        }
+
+        if let Some(two_handed) = items(EquipSlot::TwoHanded).next() {
+            let mut candidate = result;
+            candidate.equip(two_handed);
+            if better(&candidate, &result) {
+                result = candidate;
+            }
+        }
+
+        for set in ArmourSet::into_enum_iter() {
+            let piece = |slot| items(slot).find(|i| i.armour_sets.contains(&set));
+
+            let mut armour = result;
+            for slot in EquipSlot::into_enum_iter() {
+                if !matches!(slot, EquipSlot::Weapon | EquipSlot::TwoHanded) {
+                    if let Some(item) = piece(slot) {
+                        armour.equip(item);
+                    }
+                }
+            }
+
+            // Try the set pieces with the current weapon and with the weapons of the set
+            let mut candidates = vec![armour];
+            for weapon in [piece(EquipSlot::Weapon), piece(EquipSlot::TwoHanded)]
+                .into_iter()
+                .flatten()
+            {
+                let mut candidate = armour;
+                candidate.equip(weapon);
+                candidates.push(candidate);
+            }
+
+            for candidate in candidates {
+                let active = candidate.set_bonuses().iter().any(|b| b.set == set);
+                if active && better(&candidate, &result) {
+                    result = candidate;
+                }
+            }
+        }
+
+        result
+    }
+
+    /// Replace items until the [cost](Self::cost) is at most `budget`.
+    ///
+    /// Each step replaces one item by the best cheaper candidate of its slot, or removes it if
+    /// there is none, choosing the replacement which keeps the best stats according to
+    /// `compare` (see [`best`](Self::best)). Items for which `fixed` returns `true` are never
+    /// replaced, so the budget may be exceeded by them.
+    pub fn fit_budget<I: Iterator<Item = &'a Item>>(
+        &mut self,
+        budget: u64,
+        items: impl Fn(EquipSlot) -> I,
+        compare: impl Fn(&CombatStats, &CombatStats) -> Ordering,
+        context: &Context,
+        fixed: impl Fn(&Item) -> bool,
+    ) {
+        let price = |item: &Item| item.price.market.map_or(0, u64::from);
+
+        while self.cost() > budget {
+            let current = *self;
+            let mut best: Option<Self> = None;
+            for item in current.iter().filter(|&i| price(i) > 0 && !fixed(i)) {
+                let mut candidate = current;
+                candidate.unequip(item.equip_slot);
+                if let Some(cheaper) = items(item.equip_slot).find(|&i| price(i) < price(item)) {
+                    candidate.equip(cheaper);
+                }
+
+                if best.is_none_or(|b| {
+                    compare(
+                        &candidate.effective_stats(context),
+                        &b.effective_stats(context),
+                    ) == Ordering::Less
+                }) {
+                    best = Some(candidate);
+                }
+            }
+
+            match best {
+                Some(best) => *self = best,
+                None => break,
+            }
+        }
+    }
+
+    /// Equip `item` in its slot, replacing the previous item and a conflicting weapon or shield.
+    pub fn equip(&mut self, item: &'a Item) {
+        match item.equip_slot {
+            EquipSlot::TwoHanded => self.unequip(EquipSlot::Shield),
+            EquipSlot::Shield => self.unequip(EquipSlot::TwoHanded),
+            _ => {}
+        }
+
+        self.0[Self::position(item.equip_slot)] = Some(item);
+    }
+
+    /// Remove the item in `slot`.
+    pub fn unequip(&mut self, slot: EquipSlot) {
+        if self[slot].is_some() {
+            self.0[Self::position(slot)] = None;
+        }
+    }
+
+    /// Iterate over all equipped items.
+    pub fn iter(&self) -> impl Iterator<Item = &'a Item> + '_ {
+        self.0.iter().filter_map(|i| *i)
+    }
+
+    /// The equipped weapon, which may be two handed.
+    #[must_use]
+    pub fn weapon(&self) -> Option<&'a Item> {
+        self.0[EquipSlot::Weapon as usize]
+    }
+
+    /// Returns `true` if no item is equipped.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(Option::is_none)
+    }
+
+    /// Sum of the stats of all equipped items.
+    #[must_use]
+    pub fn combat_stats(&self) -> CombatStats {
+        let mut result = CombatStats::default();
+        for item in self.iter() {
+            result += &item.combat_stats;
+        }
+        result
+    }
+
+    /// Sum of the stats of all equipped items, including the conditional bonuses which apply in
+    /// `context` and the set effects converted to flat bonuses.
+    ///
+    /// See [`Item::effective_stats`] and [`SetBonus::apply_to`](crate::SetBonus::apply_to).
+    #[must_use]
+    pub fn effective_stats(&self, context: &Context) -> CombatStats {
+        let mut result = CombatStats::default();
+        for item in self.iter() {
+            result += &item.effective_stats(context);
+        }
+        for bonus in self.set_bonuses() {
+            bonus.apply_to(&mut result);
+        }
+        result
+    }
+
+    /// Total weight of all equipped items in kilograms.
+    #[must_use]
+    pub fn weight(&self) -> f32 {
+        self.iter().map(|i| i.weight).sum()
+    }
+
+    /// Total Grand Exchange price of all equipped items. Items without a price are not counted.
+    #[must_use]
+    pub fn cost(&self) -> u64 {
+        self.iter()
+            .filter_map(|i| i.price.market)
+            .map(u64::from)
+            .sum()
+    }
+
+    /// Requirements to equip all items, with the highest level for each type of requirement.
+    #[must_use]
+    pub fn requirements(&self) -> Vec<Requirement> {
+        let mut result: Vec<Requirement> = Vec::new();
+        for requirement in self.iter().flat_map(|i| &i.requirements) {
+            if let Some(r) = result
+                .iter_mut()
+                .find(|r| r.requirement == requirement.requirement)
+            {
+                r.level = r.level.max(requirement.level);
+            } else {
+                result.push(requirement.clone());
+            }
+        }
+        result
+    }
+
+    /// Two handed items are stored in the weapon slot.
+    fn position(slot: EquipSlot) -> usize {
+        match slot {
+            EquipSlot::TwoHanded => EquipSlot::Weapon as usize,
+            _ => slot as usize,
+        }
+    }
+}
+
+impl<'a> Index<EquipSlot> for Loadout<'a> {
+    type Output = Option<&'a Item>;
+
+    fn index(&self, slot: EquipSlot) -> &Self::Output {
+        let item = &self.0[Self::position(slot)];
+        match (slot, item) {
+            (EquipSlot::TwoHanded, Some(i)) if i.equip_slot != EquipSlot::TwoHanded => &None,
+            (EquipSlot::Weapon, Some(i)) if i.equip_slot != EquipSlot::Weapon => &None,
+            _ => item,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{RequirementType, Skill};
+
+    use super::*;
+
+    fn item(name: &str, equip_slot: EquipSlot, strength: i16) -> Item {
+        Item::test(name, equip_slot)
+            .with_weight(1.5)
+            .with_stats(|s| s.melee_strength = strength)
+    }
+
+    fn by_strength(a: &CombatStats, b: &CombatStats) -> Ordering {
+        b.melee_strength.cmp(&a.melee_strength)
+    }
+
+    #[test]
+    fn exclusivity() {
+        let mut whip = item("Abyssal whip", EquipSlot::Weapon, 82);
+        whip.price.market = Some(1_500_000);
+        let defender = item("Dragon defender", EquipSlot::Shield, 6);
+        let godsword = item("Bandos godsword", EquipSlot::TwoHanded, 132);
+
+        let mut loadout = Loadout::new();
+        loadout.equip(&whip);
+        loadout.equip(&defender);
+        assert_eq!(loadout.iter().count(), 2);
+        assert_eq!(loadout.cost(), 1_500_000);
+
+        loadout.equip(&godsword);
+        assert!(loadout[EquipSlot::Shield].is_none());
+        assert!(loadout[EquipSlot::Weapon].is_none());
+        assert_eq!(
+            loadout[EquipSlot::TwoHanded].unwrap().name,
+            "Bandos godsword"
+        );
+
+        loadout.equip(&defender);
+        assert!(loadout[EquipSlot::TwoHanded].is_none());
+        assert_eq!(loadout.iter().count(), 1);
+        assert_eq!(loadout.weight(), 1.5);
+    }
+
+    #[test]
+    fn best() {
+        let whip = item("Abyssal whip", EquipSlot::Weapon, 82);
+        let defender = item("Dragon defender", EquipSlot::Shield, 6);
+        let godsword = item("Bandos godsword", EquipSlot::TwoHanded, 132);
+        let maul = item("Granite maul", EquipSlot::TwoHanded, 79);
+        let helm = item("Helm of neitiznot", EquipSlot::Head, 3);
+
+        let best = |two_handed| {
+            Loadout::best(
+                |slot| {
+                    match slot {
+                        EquipSlot::Weapon => Some(&whip),
+                        EquipSlot::Shield => Some(&defender),
+                        EquipSlot::TwoHanded => Some(two_handed),
+                        EquipSlot::Head => Some(&helm),
+                        _ => None,
+                    }
+                    .into_iter()
+                },
+                by_strength,
+                &Context::default(),
+            )
+        };
+
+        let loadout = best(&godsword);
+        assert_eq!(loadout.combat_stats().melee_strength, 135);
+        assert!(loadout[EquipSlot::Shield].is_none());
+
+        let loadout = best(&maul);
+        assert_eq!(loadout.combat_stats().melee_strength, 91);
+        assert!(loadout[EquipSlot::TwoHanded].is_none());
+    }
+
+    #[test]
+    fn fit_budget() {
+        let priced = |name, slot, strength, price| {
+            let mut item = item(name, slot, strength);
+            item.price.market = Some(price);
+            item
+        };
+        let torture = priced("Amulet of torture", EquipSlot::Neck, 10, 15_000_000);
+        let fury = priced("Amulet of fury", EquipSlot::Neck, 8, 2_000_000);
+        let strength = priced("Amulet of strength", EquipSlot::Neck, 10, 1_000);
+        let torso = item("Fighter torso", EquipSlot::Body, 4);
+        let bandos = priced("Bandos chestplate", EquipSlot::Body, 4, 18_000_000);
+        let whip = priced("Abyssal whip", EquipSlot::Weapon, 82, 1_500_000);
+        let candidates = |slot| {
+            match slot {
+                EquipSlot::Neck => vec![&torture, &fury, &strength],
+                EquipSlot::Body => vec![&bandos, &torso],
+                EquipSlot::Weapon => vec![&whip],
+                _ => Vec::new(),
+            }
+            .into_iter()
+        };
+
+        let mut loadout = Loadout::best(candidates, by_strength, &Context::default());
+        assert_eq!(loadout.cost(), 34_500_000);
+        loadout.fit_budget(
+            2_000_000,
+            candidates,
+            by_strength,
+            &Context::default(),
+            |_| false,
+        );
+        let names: Vec<_> = loadout.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["Amulet of strength", "Abyssal whip", "Fighter torso"]
+        );
+        assert_eq!(loadout.cost(), 1_501_000);
+
+        // Fixed items are kept, even if that exceeds the budget
+        loadout.fit_budget(
+            1_000_000,
+            candidates,
+            by_strength,
+            &Context::default(),
+            |i| i.name == "Abyssal whip",
+        );
+        assert_eq!(loadout.cost(), 1_500_000);
+        assert_eq!(loadout.iter().count(), 2);
+        loadout.fit_budget(0, candidates, by_strength, &Context::default(), |_| false);
+        let names: Vec<_> = loadout.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["Fighter torso"]);
+    }
+
+    #[test]
+    fn requirements() {
+        let mut whip = item("Abyssal whip", EquipSlot::Weapon, 82);
+        whip.requirements.push(Requirement {
+            requirement: RequirementType::Skill(Skill::Attack),
+            level: 70,
+        });
+        let mut defender = item("Dragon defender", EquipSlot::Shield, 6);
+        defender.requirements.push(Requirement {
+            requirement: RequirementType::Skill(Skill::Attack),
+            level: 60,
+        });
+        defender.requirements.push(Requirement {
+            requirement: RequirementType::Skill(Skill::Defence),
+            level: 60,
+        });
+
+        let mut loadout = Loadout::new();
+        loadout.equip(&whip);
+        loadout.equip(&defender);
+
+        let requirements = loadout.requirements();
+        assert_eq!(requirements.len(), 2);
+        assert_eq!(requirements[0].level, 70);
+        assert_eq!(requirements[1].level, 60);
+    }
+}
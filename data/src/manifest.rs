@@ -0,0 +1,29 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Name of the manifest file next to the data files.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Record of the last build of the data files, stored as JSON next to them.
+///
+/// The data-processor uses it to skip rebuilding unchanged data, the app to cache data files by
+/// their content hash.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The item data file, if it was built.
+    #[serde(default)]
+    pub items: Option<DataFile>,
+    /// The monster data file, if it was built.
+    #[serde(default)]
+    pub monsters: Option<DataFile>,
+}
+
+/// One data file of the [`Manifest`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataFile {
+    /// Hashes or versions of everything the file was built from, by name.
+    pub inputs: BTreeMap<String, String>,
+    /// Hexadecimal [`content_hash`](crate::content_hash) of the file.
+    pub hash: String,
+}
@@ -0,0 +1,256 @@
+use std::{collections::HashMap, iter::FromIterator};
+
+use enum_iterator::IntoEnumIterator;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{normalize_name, Context, DamageType, DamageTypeStats, Target};
+
+/// Monster attributes which are relevant for equipment effects.
+///
+/// See [Monster attribute](https://oldschool.runescape.wiki/w/Monster_attribute).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize, IntoEnumIterator)]
+pub enum MonsterAttribute {
+    /// Demons, weak to demonbane weapons.
+    Demon,
+    /// Dragons, weak to dragonbane weapons.
+    Dragon,
+    /// Fiery monsters.
+    Fiery,
+    /// Golems.
+    Golem,
+    /// Kalphites, weak to the keris.
+    Kalphite,
+    /// Leafy monsters, weak to leaf-bladed weapons.
+    Leafy,
+    /// Penance monsters.
+    Penance,
+    /// Rats.
+    Rat,
+    /// Shades.
+    Shade,
+    /// Spectral monsters.
+    Spectral,
+    /// Undead monsters, weak to the salve amulet.
+    Undead,
+    /// Vampyres.
+    Vampyre,
+    /// Monsters in the Chambers of Xeric.
+    Xerician,
+}
+
+/// Main monster data struct.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Monster {
+    /// The monster's OSRS id.
+    pub id: u32,
+    /// The OSRS ids of all monsters represented by this monster, including [`id`](Self::id).
+    ///
+    /// Contains more than one entry if variants with identical stats were merged into this one.
+    pub source_ids: Vec<u32>,
+    /// The monster's in-game name.
+    pub name: String,
+    /// The monster's wiki URL.
+    pub wiki_url: String,
+    /// True if the monster is only found in members worlds.
+    pub members: bool,
+    /// The monster's combat level.
+    pub combat_level: u16,
+    /// The monster's hitpoints.
+    pub hitpoints: u16,
+    /// Width of the monster in tiles.
+    pub size: u8,
+    /// The monster's defence level.
+    pub defence_level: u16,
+    /// The monster's magic level.
+    pub magic_level: u16,
+    /// The monster's defence bonuses.
+    pub defence: DamageTypeStats,
+    /// Attributes of the monster.
+    pub attributes: Vec<MonsterAttribute>,
+    /// True if the monster can be assigned as a slayer task.
+    pub slayer_monster: bool,
+}
+
+impl Monster {
+    /// Returns `true` if the monster has `attribute`.
+    #[must_use]
+    pub fn has_attribute(&self, attribute: MonsterAttribute) -> bool {
+        self.attributes.contains(&attribute)
+    }
+
+    /// The damage types the monster has the lowest defence bonus against.
+    #[must_use]
+    pub fn weaknesses(&self) -> Vec<DamageType> {
+        let lowest = DamageType::into_enum_iter()
+            .map(|t| self.defence[t])
+            .min()
+            .unwrap_or_default();
+        DamageType::into_enum_iter()
+            .filter(|&t| self.defence[t] == lowest)
+            .collect()
+    }
+
+    /// The defensive properties of the monster used in combat calculations.
+    #[must_use]
+    pub fn target(&self) -> Target {
+        Target {
+            defence_level: self.defence_level,
+            magic_level: self.magic_level,
+            defence: self.defence,
+            context: Context {
+                attributes: self.attributes.clone(),
+                ..Context::default()
+            },
+        }
+    }
+}
+
+/// Contains all monsters, sorted by name.
+///
+/// Monsters can be looked up by id or name, see [`MonsterDatabase::get_by_id`] and
+/// [`MonsterDatabase::find_by_name`].
+#[derive(Debug, Default)]
+pub struct MonsterDatabase {
+    monsters: Vec<Monster>,
+    ids: HashMap<u32, usize>,
+    normalized_names: HashMap<String, usize>,
+}
+
+impl MonsterDatabase {
+    /// Get the amount of monsters in the database.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.monsters.len()
+    }
+
+    /// Return true if no monster is stored in the database.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.monsters.is_empty()
+    }
+
+    /// Iterate over all monsters.
+    pub fn iter(&self) -> impl Iterator<Item = &Monster> {
+        self.monsters.iter()
+    }
+
+    /// Get a monster by its OSRS id.
+    ///
+    /// Ids of merged variants resolve to the monster they were merged into.
+    #[must_use]
+    pub fn get_by_id(&self, id: u32) -> Option<&Monster> {
+        self.ids.get(&id).map(|&i| &self.monsters[i])
+    }
+
+    /// Get a monster by name, ignoring case, punctuation and surplus whitespace.
+    ///
+    /// If multiple monsters share a name, the one with the lowest combat level is returned.
+    #[must_use]
+    pub fn find_by_name(&self, name: &str) -> Option<&Monster> {
+        self.normalized_names
+            .get(&normalize_name(name))
+            .map(|&i| &self.monsters[i])
+    }
+
+    fn reindex(&mut self) {
+        self.ids.clear();
+        self.normalized_names.clear();
+
+        for (idx, monster) in self.monsters.iter().enumerate() {
+            for &id in &monster.source_ids {
+                self.ids.insert(id, idx);
+            }
+            self.normalized_names
+                .entry(normalize_name(&monster.name))
+                .or_insert(idx);
+        }
+    }
+}
+
+impl FromIterator<Monster> for MonsterDatabase {
+    fn from_iter<T: IntoIterator<Item = Monster>>(iter: T) -> Self {
+        let mut monsters: Vec<_> = iter.into_iter().collect();
+        monsters.sort_unstable_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then(a.combat_level.cmp(&b.combat_level))
+                .then(a.id.cmp(&b.id))
+        });
+
+        let mut result = Self {
+            monsters,
+            ..Self::default()
+        };
+        result.reindex();
+        result
+    }
+}
+
+impl Serialize for MonsterDatabase {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.monsters.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MonsterDatabase {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut result = Self {
+            monsters: Vec::deserialize(deserializer)?,
+            ..Self::default()
+        };
+        result.reindex();
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monster(id: u32, name: &str, combat_level: u16) -> Monster {
+        Monster {
+            id,
+            source_ids: vec![id, id + 1000],
+            name: name.to_string(),
+            wiki_url: String::new(),
+            members: true,
+            combat_level,
+            hitpoints: 100,
+            size: 1,
+            defence_level: 100,
+            magic_level: 1,
+            defence: DamageTypeStats::new(&[20, 10, 10, 50, 30]),
+            attributes: vec![MonsterAttribute::Undead],
+            slayer_monster: false,
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        let database: MonsterDatabase = [
+            monster(2, "Skeleton", 45),
+            monster(1, "Skeleton", 22),
+            monster(3, "Ankou", 75),
+        ]
+        .into_iter()
+        .collect();
+
+        let bytes = database.encode(0);
+        let (_, database) = MonsterDatabase::decode(&bytes).unwrap();
+
+        assert_eq!(database.len(), 3);
+        assert_eq!(database.iter().next().unwrap().name, "Ankou");
+        assert_eq!(database.get_by_id(1002).unwrap().combat_level, 45);
+        assert_eq!(database.find_by_name("skeleton").unwrap().id, 1);
+        assert!(database.find_by_name("Zombie").is_none());
+    }
+
+    #[test]
+    fn properties() {
+        let monster = monster(1, "Skeleton", 22);
+        assert!(monster.has_attribute(MonsterAttribute::Undead));
+        assert!(!monster.has_attribute(MonsterAttribute::Demon));
+        assert_eq!(monster.weaknesses(), [DamageType::Slash, DamageType::Crush]);
+        assert_eq!(monster.target().defence_level, 100);
+    }
+}
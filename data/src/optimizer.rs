@@ -0,0 +1,772 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use enum_iterator::IntoEnumIterator;
+
+use crate::{
+    ArmourSet, Attacker, CombatClass, CombatOption, CombatStats, Damage, DamageType, EquipSlot,
+    Item, Loadout, PlayerLevels, Prayer, Target, SPELL_ATTACK_DELAY,
+};
+
+/// Maximum number of partial loadouts kept while combining the armour slots.
+const MAX_FRONT: usize = 64;
+
+/// Slots combined by the armour search. Weapons, shields and ammunition depend on the weapon and
+/// are added separately.
+const ARMOUR_SLOTS: [EquipSlot; 8] = [
+    EquipSlot::Head,
+    EquipSlot::Cape,
+    EquipSlot::Neck,
+    EquipSlot::Body,
+    EquipSlot::Legs,
+    EquipSlot::Hands,
+    EquipSlot::Feet,
+    EquipSlot::Ring,
+];
+
+/// A loadout found by [`Optimizer::optimize`].
+#[derive(Debug, Clone)]
+pub struct OptimizedLoadout<'a> {
+    /// The equipped items.
+    pub loadout: Loadout<'a>,
+    /// Combat option of the weapon to attack with.
+    pub option: &'a CombatOption,
+    /// Outcome of attacking the target.
+    pub damage: Damage,
+}
+
+/// Searches combinations of items for the loadouts with the highest expected damage per second
+/// against a [`Target`].
+///
+/// Items are only compared by the bonuses relevant for the damage type of an attack, items with
+/// lower accuracy and strength bonuses than another item of the same slot are pruned. Armour sets
+/// and items with [conditional bonuses](crate::ConditionalBonus) which apply against the target
+/// are always considered, since their effects are not captured by their bonuses. Partial armour
+/// sets are only found if their pieces are good on their own.
+///
+/// Magic attacks are only considered if [`spell_max_hit`](Self::spell_max_hit) is set. The
+/// ammunition used by a ranged weapon is guessed from the names of weapon and ammunition.
+///
+/// With a [`budget`](Self::budget), cheaper items are kept even if their bonuses are lower, and
+/// only loadouts whose [cost](Loadout::cost) is within the budget are returned.
+#[derive(Debug, Clone)]
+pub struct Optimizer<'a> {
+    /// The player's levels.
+    pub levels: &'a PlayerLevels,
+    /// Prayers the player can use. The first prayer boosting the combat class of an attack is
+    /// active.
+    pub prayers: Vec<Prayer>,
+    /// Base max hit of the spell being cast, `0` to ignore magic attacks.
+    pub spell_max_hit: u16,
+    /// Current hitpoints of the player, `None` for full hitpoints.
+    pub current_hitpoints: Option<u8>,
+    /// Highest total Grand Exchange price of a loadout, `None` if unlimited.
+    pub budget: Option<u64>,
+    /// The opponent.
+    pub target: &'a Target,
+}
+
+impl Optimizer<'_> {
+    /// Find the `count` loadouts with the highest expected damage per second, the best first.
+    ///
+    /// `items` yields the candidates for a slot, e.g. the filtered items of a
+    /// [`Database`](crate::Database).
+    pub fn optimize<'i, I: Iterator<Item = &'i Item>>(
+        &self,
+        items: impl Fn(EquipSlot) -> I,
+        count: usize,
+    ) -> Vec<OptimizedLoadout<'i>> {
+        let budget = self.budget.unwrap_or(u64::MAX);
+        let candidates: Vec<Vec<&'i Item>> = EquipSlot::into_enum_iter()
+            .map(|s| {
+                items(s)
+                    .filter(|&i| price(i, self.budget) <= budget)
+                    .collect()
+            })
+            .collect();
+        let candidates = |slot: EquipSlot| candidates[slot as usize].as_slice();
+
+        let mut results = Vec::new();
+        if count == 0 {
+            return results;
+        }
+
+        for damage_type in DamageType::into_enum_iter() {
+            if damage_type == DamageType::Magic && self.spell_max_hit == 0 {
+                continue;
+            }
+
+            let weapons = self.weapons(&candidates, damage_type);
+            if weapons.is_empty() {
+                continue;
+            }
+
+            let fronts: Vec<Vec<Partial>> = EquipSlot::into_enum_iter()
+                .map(|slot| {
+                    let options = candidates(slot)
+                        .iter()
+                        .map(|&i| Partial::of(i, damage_type, self.budget));
+                    pareto(options.chain([Partial::default()]).collect())
+                })
+                .collect();
+
+            for variant in self.variants(&candidates, damage_type) {
+                for two_handed in [false, true] {
+                    let weapons: Vec<_> = weapons
+                        .iter()
+                        .filter(|w| {
+                            w.two_handed == two_handed
+                                && variant
+                                    .weapon_set
+                                    .is_none_or(|set| w.item.armour_sets.contains(&set))
+                        })
+                        .collect();
+                    if weapons.is_empty() {
+                        continue;
+                    }
+
+                    let front = variant.combine(&fronts, damage_type, !two_handed, self.budget);
+                    for weapon in weapons {
+                        let ammunition =
+                            ammunition(&candidates, weapon.item, damage_type, self.budget);
+                        for ammunition in ammunition {
+                            let cost =
+                                weapon.price + ammunition.map_or(0, |i| price(i, self.budget));
+                            for partial in front.iter().filter(|p| p.price + cost <= budget) {
+                                let mut loadout = Loadout::new();
+                                for &item in partial.items.iter().chain(&ammunition) {
+                                    loadout.equip(item);
+                                }
+                                loadout.equip(weapon.item);
+
+                                let result = self.evaluate(loadout, weapon.option);
+                                insert(&mut results, result, count);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        results
+    }
+
+    /// Calculate the damage of attacking the target with `loadout`.
+    fn evaluate<'i>(&self, loadout: Loadout<'i>, option: &'i CombatOption) -> OptimizedLoadout<'i> {
+        let class = option.damage_type.class();
+        let mut attacker = Attacker::new(self.levels, &loadout, option, self.spell_max_hit);
+        if let Some(hitpoints) = self.current_hitpoints {
+            attacker.current_hitpoints = hitpoints;
+        }
+        attacker.prayer = self.prayers.iter().copied().find(|p| p.class() == class);
+
+        OptimizedLoadout {
+            loadout,
+            option,
+            damage: attacker.against(self.target),
+        }
+    }
+
+    /// Returns `true` if `item` has an effect against the target which is not captured by its
+    /// bonuses.
+    fn is_special(&self, item: &Item, damage_type: DamageType) -> bool {
+        let class = damage_type.class();
+        item.armour_sets.iter().any(|s| s.boosts(damage_type))
+            || self
+                .target
+                .context
+                .active(&item.conditional_bonuses)
+                .iter()
+                .any(|b| b.class == class)
+    }
+
+    /// Combat options of all weapons attacking with `damage_type`, without weapons which are
+    /// worse than another weapon in every aspect.
+    fn weapons<'c, 'i: 'c>(
+        &self,
+        candidates: &impl Fn(EquipSlot) -> &'c [&'i Item],
+        damage_type: DamageType,
+    ) -> Vec<Weapon<'i>> {
+        let weapons: Vec<_> = [EquipSlot::Weapon, EquipSlot::TwoHanded]
+            .into_iter()
+            .flat_map(|slot| candidates(slot).iter())
+            .filter_map(|&item| item.weapon_data.as_ref().map(|w| (item, w)))
+            .flat_map(|(item, data)| {
+                data.combat_options
+                    .iter()
+                    .filter(|o| o.damage_type == damage_type)
+                    .map(move |option| Weapon {
+                        item,
+                        option,
+                        two_handed: item.equip_slot == EquipSlot::TwoHanded,
+                        attack_delay: match damage_type {
+                            DamageType::Magic => SPELL_ATTACK_DELAY,
+                            _ => data.attack_delay,
+                        },
+                        key: key(&item.combat_stats, damage_type),
+                        price: price(item, self.budget),
+                        special: self.is_special(item, damage_type),
+                    })
+            })
+            .collect();
+
+        weapons
+            .iter()
+            .enumerate()
+            .filter(|&(i, weapon)| {
+                weapon.special
+                    || !weapons
+                        .iter()
+                        .enumerate()
+                        .any(|(j, other)| j != i && other.dominates(weapon, j < i))
+            })
+            .map(|(_, weapon)| weapon.clone())
+            .collect()
+    }
+
+    /// Items which are always tried: the pieces of armour sets boosting `damage_type` and items
+    /// with conditional bonuses which apply against the target.
+    fn variants<'c, 'i: 'c>(
+        &self,
+        candidates: &impl Fn(EquipSlot) -> &'c [&'i Item],
+        damage_type: DamageType,
+    ) -> Vec<Variant<'i>> {
+        let mut sets = vec![Variant::default()];
+        for set in ArmourSet::into_enum_iter().filter(|s| s.boosts(damage_type)) {
+            let mut combinations = vec![Vec::new()];
+            for &slot in set.slots().iter().filter(|&&s| s != EquipSlot::Weapon) {
+                let pieces: Vec<_> = candidates(slot)
+                    .iter()
+                    .filter(|i| i.armour_sets.contains(&set))
+                    .map(|&i| Partial::of(i, damage_type, self.budget))
+                    .collect();
+                let pieces: Vec<_> = pareto(pieces).iter().map(|p| p.items[0]).collect();
+                combinations = product(&combinations, &pieces);
+            }
+
+            let weapon_set = set.slots().contains(&EquipSlot::Weapon).then_some(set);
+            sets.extend(
+                combinations
+                    .into_iter()
+                    .map(|forced| Variant { forced, weapon_set }),
+            );
+        }
+
+        let class = damage_type.class();
+        let mut result = Vec::new();
+        for set in sets {
+            let mut combinations = vec![set.forced.clone()];
+            for slot in ARMOUR_SLOTS {
+                if set.forced.iter().any(|i| i.equip_slot == slot) {
+                    continue;
+                }
+
+                let special: Vec<_> = candidates(slot)
+                    .iter()
+                    .copied()
+                    .filter(|i| {
+                        self.target
+                            .context
+                            .active(&i.conditional_bonuses)
+                            .iter()
+                            .any(|b| b.class == class)
+                    })
+                    .collect();
+                let mut extended = product(&combinations, &special);
+                extended.extend(combinations);
+                combinations = extended;
+            }
+
+            result.extend(combinations.into_iter().map(|forced| Variant {
+                forced,
+                weapon_set: set.weapon_set,
+            }));
+        }
+
+        result
+    }
+}
+
+/// Combat option of a weapon considered by the search.
+#[derive(Debug, Clone)]
+struct Weapon<'a> {
+    item: &'a Item,
+    option: &'a CombatOption,
+    two_handed: bool,
+    attack_delay: u8,
+    key: (i32, i32),
+    price: u64,
+    special: bool,
+}
+
+impl Weapon<'_> {
+    /// Returns `true` if `other` is never better. Equal weapons are dominated by the weapon
+    /// listed first.
+    fn dominates(&self, other: &Self, first: bool) -> bool {
+        let comparable = self.two_handed == other.two_handed
+            && self.option.style == other.option.style
+            && ammunition_name(self.item) == ammunition_name(other.item);
+        let at_least = self.attack_delay <= other.attack_delay
+            && self.key.0 >= other.key.0
+            && self.key.1 >= other.key.1
+            && self.price <= other.price;
+        let equal = self.attack_delay == other.attack_delay
+            && self.key == other.key
+            && self.price == other.price;
+
+        comparable && at_least && (!equal || first)
+    }
+}
+
+/// Items equipped before the remaining armour slots are searched.
+#[derive(Debug, Clone, Default)]
+struct Variant<'a> {
+    forced: Vec<&'a Item>,
+    /// Set whose weapon has to be used.
+    weapon_set: Option<ArmourSet>,
+}
+
+impl<'a> Variant<'a> {
+    /// Combine the forced items with the best items of the remaining slots, without partial
+    /// loadouts exceeding the `budget`.
+    fn combine(
+        &self,
+        fronts: &[Vec<Partial<'a>>],
+        damage_type: DamageType,
+        shield: bool,
+        budget: Option<u64>,
+    ) -> Vec<Partial<'a>> {
+        let mut start = Partial::default();
+        for &item in &self.forced {
+            start = start.merge(&Partial::of(item, damage_type, budget));
+        }
+
+        let shield = shield.then_some(EquipSlot::Shield);
+        let ammunition = (damage_type != DamageType::Ranged).then_some(EquipSlot::Ammunition);
+
+        let mut front = vec![start];
+        for slot in ARMOUR_SLOTS.into_iter().chain(shield).chain(ammunition) {
+            if self.forced.iter().any(|i| i.equip_slot == slot) {
+                continue;
+            }
+
+            let options = &fronts[slot as usize];
+            front = pareto(
+                front
+                    .iter()
+                    .flat_map(|p| options.iter().map(move |o| p.merge(o)))
+                    .filter(|p| budget.is_none_or(|budget| p.price <= budget))
+                    .collect(),
+            );
+        }
+
+        front
+    }
+}
+
+/// Items of some slots with their combined accuracy and strength bonus and price.
+#[derive(Debug, Clone, Default)]
+struct Partial<'a> {
+    key: (i32, i32),
+    price: u64,
+    items: Vec<&'a Item>,
+}
+
+impl<'a> Partial<'a> {
+    fn of(item: &'a Item, damage_type: DamageType, budget: Option<u64>) -> Self {
+        Self {
+            key: key(&item.combat_stats, damage_type),
+            price: price(item, budget),
+            items: vec![item],
+        }
+    }
+
+    fn merge(&self, other: &Self) -> Self {
+        Self {
+            key: (self.key.0 + other.key.0, self.key.1 + other.key.1),
+            price: self.price + other.price,
+            items: self.items.iter().chain(&other.items).copied().collect(),
+        }
+    }
+}
+
+/// Grand Exchange price of `item` counted against the `budget`, always `0` without a budget so
+/// prices do not affect the search.
+fn price(item: &Item, budget: Option<u64>) -> u64 {
+    budget.map_or(0, |_| item.price.market.map_or(0, u64::from))
+}
+
+/// Accuracy and strength bonus for attacks of `damage_type`.
+fn key(stats: &CombatStats, damage_type: DamageType) -> (i32, i32) {
+    let strength = match damage_type.class() {
+        CombatClass::Melee => stats.melee_strength,
+        CombatClass::Ranged => stats.ranged_strength,
+        CombatClass::Magic => stats.magic_damage,
+    };
+    (i32::from(stats.attack[damage_type]), i32::from(strength))
+}
+
+/// Remove the partial loadouts with lower accuracy and strength than another one which is not
+/// more expensive.
+///
+/// If more than [`MAX_FRONT`] remain, evenly spaced ones are kept, including the cheapest and the
+/// most accurate. Without prices, the strongest is kept as well.
+fn pareto(mut partials: Vec<Partial>) -> Vec<Partial> {
+    partials.sort_unstable_by_key(|p| (p.price, Reverse(p.key)));
+
+    // Strength by accuracy of the kept partials, strength decreases as accuracy increases. A
+    // partial is dominated if a kept one has at least its accuracy and strength.
+    let mut kept: BTreeMap<i32, i32> = BTreeMap::new();
+    partials.retain(|p| {
+        let (accuracy, strength) = p.key;
+        if kept
+            .range(accuracy..)
+            .next()
+            .is_some_and(|(_, &s)| s >= strength)
+        {
+            return false;
+        }
+
+        let dominated: Vec<_> = kept
+            .range(..=accuracy)
+            .rev()
+            .take_while(|(_, &s)| s <= strength)
+            .map(|(&a, _)| a)
+            .collect();
+        for a in dominated {
+            kept.remove(&a);
+        }
+        kept.insert(accuracy, strength);
+        true
+    });
+
+    if partials.len() > MAX_FRONT {
+        let last = partials.len() - 1;
+        let keep: Vec<_> = (0..MAX_FRONT).map(|i| i * last / (MAX_FRONT - 1)).collect();
+        let mut idx = 0;
+        partials.retain(|_| {
+            idx += 1;
+            keep.binary_search(&(idx - 1)).is_ok()
+        });
+    }
+
+    partials
+}
+
+/// Extend each combination with each of `items`.
+fn product<'a>(combinations: &[Vec<&'a Item>], items: &[&'a Item]) -> Vec<Vec<&'a Item>> {
+    combinations
+        .iter()
+        .flat_map(|c| {
+            items.iter().map(move |&item| {
+                let mut combination = c.clone();
+                combination.push(item);
+                combination
+            })
+        })
+        .collect()
+}
+
+/// Part of the name of the ammunition fired by a ranged weapon, guessed from the weapon's name.
+fn ammunition_name(weapon: &Item) -> Option<&'static str> {
+    let name = weapon.name.to_lowercase();
+    if name.contains("crossbow") {
+        Some("bolt")
+    } else if name.contains("ballista") {
+        Some("javelin")
+    } else if name.contains("bow") && !name.contains("crystal") && !name.contains("faerdhinen") {
+        Some("arrow")
+    } else {
+        None
+    }
+}
+
+/// The best ammunition options for `weapon`. Contains no ammunition if the weapon does not need
+/// any, and no options if no fitting ammunition is available.
+fn ammunition<'c, 'a: 'c>(
+    candidates: &impl Fn(EquipSlot) -> &'c [&'a Item],
+    weapon: &Item,
+    damage_type: DamageType,
+    budget: Option<u64>,
+) -> Vec<Option<&'a Item>> {
+    match ammunition_name(weapon) {
+        Some(name) if damage_type == DamageType::Ranged => {
+            let fitting = candidates(EquipSlot::Ammunition)
+                .iter()
+                .filter(|i| i.name.to_lowercase().contains(name))
+                .map(|&i| Partial::of(i, damage_type, budget))
+                .collect();
+            pareto(fitting)
+                .into_iter()
+                .map(|p| Some(p.items[0]))
+                .collect()
+        }
+        _ => vec![None],
+    }
+}
+
+/// Insert `candidate` into `results`, which are sorted by descending damage per second and hold at
+/// most `count` distinct loadouts.
+fn insert<'a>(
+    results: &mut Vec<OptimizedLoadout<'a>>,
+    candidate: OptimizedLoadout<'a>,
+    count: usize,
+) {
+    let dps = candidate.damage.dps();
+    if results.len() == count && results.last().is_some_and(|r| r.damage.dps() >= dps) {
+        return;
+    }
+
+    let ids = |loadout: &Loadout| loadout.iter().map(|i| i.id).collect::<Vec<_>>();
+    let candidate_ids = ids(&candidate.loadout);
+    if results.iter().any(|r| {
+        r.option.name == candidate.option.name
+            && r.damage == candidate.damage
+            && ids(&r.loadout) == candidate_ids
+    }) {
+        return;
+    }
+
+    let idx = results.partition_point(|r| r.damage.dps() >= dps);
+    results.insert(idx, candidate);
+    results.truncate(count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AttackStyle, Condition, ConditionalBonus, MonsterAttribute, Multiplier, WeaponData,
+    };
+
+    fn item(id: u32, equip_slot: EquipSlot, attack: i16, strength: i16) -> Item {
+        Item::test(&format!("Item {id}"), equip_slot)
+            .with_id(id)
+            .with_stats(|s| {
+                s.attack[DamageType::Slash] = attack;
+                s.melee_strength = strength;
+            })
+    }
+
+    fn weapon(id: u32, equip_slot: EquipSlot, attack: i16, strength: i16, delay: u8) -> Item {
+        let mut item = item(id, equip_slot, attack, strength);
+        item.weapon_data = Some(WeaponData {
+            attack_delay: delay,
+            combat_options: [AttackStyle::Accurate, AttackStyle::Aggressive]
+                .into_iter()
+                .map(|style| CombatOption {
+                    name: format!("{style:?}"),
+                    style,
+                    damage_type: DamageType::Slash,
+                })
+                .collect(),
+        });
+        item
+    }
+
+    fn target() -> Target {
+        Target {
+            defence_level: 150,
+            magic_level: 1,
+            ..Target::default()
+        }
+    }
+
+    fn slots<'a>(items: &'a [Item]) -> impl Fn(EquipSlot) -> std::vec::IntoIter<&'a Item> + 'a {
+        move |slot| {
+            items
+                .iter()
+                .filter(|i| i.equip_slot == slot)
+                .collect::<Vec<_>>()
+                .into_iter()
+        }
+    }
+
+    #[test]
+    fn exhaustive() {
+        let items = [
+            item(1, EquipSlot::Head, 0, 10),
+            item(2, EquipSlot::Head, 30, 0),
+            item(3, EquipSlot::Head, 0, 5),
+            item(4, EquipSlot::Body, 40, 2),
+            item(5, EquipSlot::Body, 5, 12),
+            item(6, EquipSlot::Shield, 20, 5),
+            item(7, EquipSlot::Shield, 0, 8),
+            weapon(8, EquipSlot::Weapon, 60, 50, 4),
+            weapon(9, EquipSlot::TwoHanded, 90, 100, 6),
+            weapon(10, EquipSlot::Weapon, 50, 40, 5),
+        ];
+        let levels = PlayerLevels::maxed();
+        let target = target();
+        let optimizer = Optimizer {
+            levels: &levels,
+            prayers: vec![Prayer::Piety],
+            spell_max_hit: 0,
+            current_hitpoints: None,
+            budget: None,
+            target: &target,
+        };
+
+        let results = optimizer.optimize(slots(&items), 3);
+        assert_eq!(results.len(), 3);
+        assert!(results
+            .windows(2)
+            .all(|w| w[0].damage.dps() >= w[1].damage.dps()));
+
+        assert!((results[0].damage.dps() - brute_force(&optimizer, &items)).abs() < 1e-9);
+    }
+
+    /// The highest damage per second of any loadout within the budget.
+    fn brute_force(optimizer: &Optimizer, items: &[Item]) -> f64 {
+        let options = |slot| {
+            let mut options: Vec<_> = items
+                .iter()
+                .filter(|i| i.equip_slot == slot)
+                .map(Some)
+                .collect();
+            options.push(None);
+            options
+        };
+        let mut best = 0.0_f64;
+        for head in options(EquipSlot::Head) {
+            for body in options(EquipSlot::Body) {
+                for shield in options(EquipSlot::Shield) {
+                    for weapon in items.iter().filter(|i| i.weapon_data.is_some()) {
+                        let mut loadout = Loadout::new();
+                        for item in [head, body, shield].into_iter().flatten() {
+                            loadout.equip(item);
+                        }
+                        loadout.equip(weapon);
+                        if optimizer.budget.is_some_and(|b| loadout.cost() > b) {
+                            continue;
+                        }
+                        for option in &weapon.weapon_data.as_ref().unwrap().combat_options {
+                            best = best.max(optimizer.evaluate(loadout, option).damage.dps());
+                        }
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn budget() {
+        let priced = |mut item: Item, price| {
+            item.price.market = Some(price);
+            item
+        };
+        let items = [
+            priced(item(1, EquipSlot::Head, 0, 10), 500),
+            item(2, EquipSlot::Head, 0, 2),
+            priced(item(3, EquipSlot::Body, 40, 12), 2_000),
+            priced(item(4, EquipSlot::Body, 5, 6), 100),
+            priced(item(5, EquipSlot::Shield, 20, 5), 300),
+            priced(weapon(6, EquipSlot::Weapon, 60, 50, 4), 5_000),
+            priced(weapon(7, EquipSlot::Weapon, 40, 30, 4), 200),
+            priced(weapon(8, EquipSlot::TwoHanded, 90, 100, 6), 10_000),
+        ];
+        let levels = PlayerLevels::maxed();
+        let target = target();
+        for budget in [0, 300, 1_000, 2_500, 6_000, 12_000] {
+            let optimizer = Optimizer {
+                levels: &levels,
+                prayers: Vec::new(),
+                spell_max_hit: 0,
+                current_hitpoints: None,
+                budget: Some(budget),
+                target: &target,
+            };
+            let results = optimizer.optimize(slots(&items), 3);
+            assert!(results.iter().all(|r| r.loadout.cost() <= budget));
+            let best = results.first().map_or(0.0, |r| r.damage.dps());
+            assert!((best - brute_force(&optimizer, &items)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn special() {
+        let mut salve = item(1, EquipSlot::Neck, 0, 0);
+        salve.conditional_bonuses.push(ConditionalBonus::new(
+            Condition::Attribute(MonsterAttribute::Undead),
+            CombatClass::Melee,
+            Multiplier::new(6, 5),
+        ));
+        let obsidian = |mut item: Item| {
+            item.armour_sets.push(ArmourSet::Obsidian);
+            item
+        };
+        let items = [
+            salve,
+            item(2, EquipSlot::Neck, 10, 5),
+            item(3, EquipSlot::Head, 0, 5),
+            obsidian(item(4, EquipSlot::Head, 0, 0)),
+            obsidian(item(5, EquipSlot::Body, 0, 0)),
+            obsidian(item(6, EquipSlot::Legs, 0, 0)),
+            obsidian(weapon(7, EquipSlot::Weapon, 40, 40, 5)),
+            weapon(8, EquipSlot::Weapon, 45, 40, 5),
+        ];
+        let levels = PlayerLevels::maxed();
+        let target = target();
+        let mut undead = target.clone();
+        undead.context.attributes.push(MonsterAttribute::Undead);
+
+        let best = |target| {
+            let optimizer = Optimizer {
+                levels: &levels,
+                prayers: Vec::new(),
+                spell_max_hit: 0,
+                current_hitpoints: None,
+                budget: None,
+                target,
+            };
+            let best = &optimizer.optimize(slots(&items), 1)[0];
+            best.loadout.iter().map(|i| i.id).collect::<Vec<_>>()
+        };
+
+        assert_eq!(best(&target), [4, 2, 7, 5, 6]);
+        assert_eq!(best(&undead), [4, 1, 7, 5, 6]);
+    }
+
+    #[test]
+    fn pruning() {
+        let items = [
+            item(1, EquipSlot::Head, 10, 10),
+            item(2, EquipSlot::Head, 10, 5),
+            item(3, EquipSlot::Head, 5, 20),
+            item(4, EquipSlot::Head, 10, 10),
+        ];
+        let front = pareto(
+            items
+                .iter()
+                .map(|i| Partial::of(i, DamageType::Slash, None))
+                .collect(),
+        );
+        let keys: Vec<_> = front.iter().map(|p| p.key).collect();
+        assert_eq!(keys, [(10, 10), (5, 20)]);
+
+        let sword = weapon(5, EquipSlot::Weapon, 10, 10, 4);
+        let slow = weapon(6, EquipSlot::Weapon, 20, 10, 5);
+        let worse = weapon(7, EquipSlot::Weapon, 5, 10, 4);
+        let weapons = [&sword, &slow, &worse];
+        let candidates = |slot: EquipSlot| match slot {
+            EquipSlot::Weapon => &weapons[..],
+            _ => &[],
+        };
+        let target = target();
+        let levels = PlayerLevels::maxed();
+        let optimizer = Optimizer {
+            levels: &levels,
+            prayers: Vec::new(),
+            spell_max_hit: 0,
+            current_hitpoints: None,
+            budget: None,
+            target: &target,
+        };
+        let ids: Vec<_> = optimizer
+            .weapons(&candidates, DamageType::Slash)
+            .iter()
+            .map(|w| w.item.id)
+            .collect();
+        assert_eq!(ids, [5, 5, 6, 6]);
+    }
+}
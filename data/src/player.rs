@@ -0,0 +1,246 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use enum_iterator::IntoEnumIterator;
+use serde::{Deserialize, Serialize};
+
+use crate::{Item, Requirement, RequirementType, Skill};
+
+/// Skill levels of a player.
+///
+/// Serialized as a map from skill name to level to allow manual inspection / editing.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct PlayerLevels(HashMap<Skill, u8>);
+
+impl PlayerLevels {
+    /// Highest level obtainable in each skill.
+    pub const MAX_LEVEL: u8 = 99;
+
+    /// Create levels of a new account: 10 Hitpoints and 1 in every other skill.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Create levels with every skill at [`MAX_LEVEL`](Self::MAX_LEVEL).
+    #[must_use]
+    pub fn maxed() -> Self {
+        Self(
+            Skill::into_enum_iter()
+                .map(|s| (s, Self::MAX_LEVEL))
+                .collect(),
+        )
+    }
+
+    /// Lowest possible level in `skill`.
+    #[must_use]
+    pub fn min_level(skill: Skill) -> u8 {
+        match skill {
+            Skill::Hitpoints => 10,
+            _ => 1,
+        }
+    }
+
+    /// Get the level in `skill`.
+    #[must_use]
+    pub fn get(&self, skill: Skill) -> u8 {
+        self.0
+            .get(&skill)
+            .copied()
+            .unwrap_or_else(|| Self::min_level(skill))
+    }
+
+    /// Set the level in `skill`. Values outside of the valid range are clamped.
+    pub fn set(&mut self, skill: Skill, level: u8) {
+        let level = level.clamp(Self::min_level(skill), Self::MAX_LEVEL);
+        self.0.insert(skill, level);
+    }
+
+    /// Calculate the [combat level](https://oldschool.runescape.wiki/w/Combat_level).
+    #[must_use]
+    pub fn combat_level(&self) -> u8 {
+        let level = |skill| u32::from(self.get(skill));
+
+        // All terms are scaled by 40 to stay in integer arithmetic
+        let base =
+            10 * (level(Skill::Defence) + level(Skill::Hitpoints) + level(Skill::Prayer) / 2);
+        let melee = 13 * (level(Skill::Attack) + level(Skill::Strength));
+        let ranged = 13 * (3 * level(Skill::Ranged) / 2);
+        let magic = 13 * (3 * level(Skill::Magic) / 2);
+
+        let combat = (base + melee.max(ranged).max(magic)) / 40;
+        u8::try_from(combat).unwrap_or(u8::MAX)
+    }
+}
+
+impl Default for PlayerLevels {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Quests, achievement diaries and combat achievements completed by a player.
+///
+/// Uses individual named fields to allow manual inspection / editing.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Unlocks {
+    #[serde(default)]
+    quests: BTreeSet<String>,
+    #[serde(default)]
+    diaries: BTreeMap<String, u8>,
+    #[serde(default)]
+    combat_achievements: u8,
+}
+
+impl Unlocks {
+    /// Returns `true` if the named quest is completed.
+    #[must_use]
+    pub fn quest(&self, quest: &str) -> bool {
+        self.quests.contains(quest)
+    }
+
+    /// Mark the named quest as completed or not completed.
+    pub fn set_quest(&mut self, quest: &str, completed: bool) {
+        if completed {
+            self.quests.insert(quest.to_string());
+        } else {
+            self.quests.remove(quest);
+        }
+    }
+
+    /// Highest completed diary tier of the named region, `0` if none is completed.
+    #[must_use]
+    pub fn diary(&self, region: &str) -> u8 {
+        self.diaries.get(region).copied().unwrap_or(0)
+    }
+
+    /// Set the highest completed diary tier of the named region.
+    pub fn set_diary(&mut self, region: &str, tier: u8) {
+        if tier == 0 {
+            self.diaries.remove(region);
+        } else {
+            self.diaries.insert(region.to_string(), tier);
+        }
+    }
+
+    /// Highest completed combat achievement tier, `0` if none is completed.
+    #[must_use]
+    pub fn combat_achievements(&self) -> u8 {
+        self.combat_achievements
+    }
+
+    /// Set the highest completed combat achievement tier.
+    pub fn set_combat_achievements(&mut self, tier: u8) {
+        self.combat_achievements = tier;
+    }
+}
+
+impl Requirement {
+    /// Returns `true` if a player with `levels` and `unlocks` fulfills this requirement.
+    #[must_use]
+    pub fn is_met(&self, levels: &PlayerLevels, unlocks: &Unlocks) -> bool {
+        let level = match &self.requirement {
+            RequirementType::Skill(skill) => levels.get(*skill),
+            RequirementType::CombatLevel => levels.combat_level(),
+            RequirementType::Quest(quest) => return unlocks.quest(quest),
+            RequirementType::Diary(region) => unlocks.diary(region),
+            RequirementType::CombatAchievements => unlocks.combat_achievements(),
+        };
+        level >= self.level
+    }
+}
+
+impl Item {
+    /// Returns `true` if a player with `levels` and `unlocks` meets all requirements to equip this
+    /// item.
+    #[must_use]
+    pub fn usable_by(&self, levels: &PlayerLevels, unlocks: &Unlocks) -> bool {
+        self.requirements.iter().all(|r| r.is_met(levels, unlocks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combat_level() {
+        assert_eq!(PlayerLevels::new().combat_level(), 3);
+        assert_eq!(PlayerLevels::maxed().combat_level(), 126);
+
+        let mut pure = PlayerLevels::new();
+        pure.set(Skill::Attack, 60);
+        pure.set(Skill::Strength, 99);
+        pure.set(Skill::Hitpoints, 85);
+        pure.set(Skill::Prayer, 52);
+        assert_eq!(pure.combat_level(), 79);
+
+        let mut ranged = PlayerLevels::new();
+        ranged.set(Skill::Ranged, 99);
+        ranged.set(Skill::Hitpoints, 99);
+        ranged.set(Skill::Defence, 1);
+        assert_eq!(ranged.combat_level(), 73);
+    }
+
+    #[test]
+    fn clamping() {
+        let mut levels = PlayerLevels::new();
+        levels.set(Skill::Hitpoints, 1);
+        levels.set(Skill::Attack, 120);
+        assert_eq!(levels.get(Skill::Hitpoints), 10);
+        assert_eq!(levels.get(Skill::Attack), 99);
+    }
+
+    #[test]
+    fn requirements() {
+        let mut levels = PlayerLevels::new();
+        levels.set(Skill::Defence, 40);
+
+        let unlocks = Unlocks::default();
+
+        let requirement = |requirement, level| Requirement { requirement, level };
+        assert!(requirement(RequirementType::Skill(Skill::Defence), 40).is_met(&levels, &unlocks));
+        assert!(!requirement(RequirementType::Skill(Skill::Defence), 70).is_met(&levels, &unlocks));
+        assert!(requirement(RequirementType::CombatLevel, 3).is_met(&levels, &unlocks));
+        assert!(!requirement(RequirementType::CombatLevel, 100).is_met(&levels, &unlocks));
+    }
+
+    #[test]
+    fn unlocks() {
+        let levels = PlayerLevels::new();
+        let mut unlocks = Unlocks::default();
+
+        let quest = Requirement {
+            requirement: RequirementType::Quest("Monkey Madness I".to_string()),
+            level: 0,
+        };
+        let diary = Requirement {
+            requirement: RequirementType::Diary("Ardougne".to_string()),
+            level: 3,
+        };
+        let combat_achievements = Requirement {
+            requirement: RequirementType::CombatAchievements,
+            level: 2,
+        };
+        assert!(!quest.is_met(&levels, &unlocks));
+        assert!(!diary.is_met(&levels, &unlocks));
+        assert!(!combat_achievements.is_met(&levels, &unlocks));
+
+        unlocks.set_quest("Monkey Madness I", true);
+        unlocks.set_diary("Ardougne", 4);
+        unlocks.set_combat_achievements(2);
+        assert!(quest.is_met(&levels, &unlocks));
+        assert!(diary.is_met(&levels, &unlocks));
+        assert!(combat_achievements.is_met(&levels, &unlocks));
+
+        unlocks.set_quest("Monkey Madness I", false);
+        unlocks.set_diary("Ardougne", 2);
+        assert!(!quest.is_met(&levels, &unlocks));
+        assert!(!diary.is_met(&levels, &unlocks));
+
+        assert_eq!(diary.name(), "Ardougne Diary");
+        assert_eq!(diary.level_name(), "Hard");
+        assert_eq!(combat_achievements.level_name(), "Medium");
+        assert_eq!(quest.level_name(), "");
+    }
+}
@@ -0,0 +1,119 @@
+use crate::{Item, Loadout};
+
+/// Items kept and lost when dying in the wilderness with a [`Loadout`].
+#[derive(Debug, Clone, Default)]
+pub struct Risk<'a> {
+    /// Items kept on death, the most valuable first.
+    pub kept: Vec<&'a Item>,
+    /// Items lost on death, the most valuable first.
+    pub lost: Vec<&'a Item>,
+    /// Grand Exchange value of the lost tradeable items. Items without a price count with their
+    /// store price.
+    pub gp_lost: u64,
+    /// Coins needed to reclaim or repair the lost untradeable items.
+    pub reclaim_cost: u64,
+}
+
+impl<'a> Risk<'a> {
+    /// Number of items kept on death.
+    ///
+    /// Unskulled players keep 3 items, skulled players none. Protect Item keeps one more.
+    #[must_use]
+    pub fn kept_count(skulled: bool, protect_item: bool) -> usize {
+        let base = if skulled { 0 } else { 3 };
+        base + usize::from(protect_item)
+    }
+
+    /// Determine the risk of dying with `loadout`. The most valuable items by
+    /// [`Price::death_value`](crate::Price::death_value) are kept.
+    #[must_use]
+    pub fn new(loadout: &Loadout<'a>, skulled: bool, protect_item: bool) -> Self {
+        let mut items: Vec<_> = loadout.iter().collect();
+        items.sort_by_key(|i| std::cmp::Reverse(i.price.death_value()));
+
+        let lost = items.split_off(Self::kept_count(skulled, protect_item).min(items.len()));
+
+        let mut gp_lost = 0;
+        let mut reclaim_cost = 0;
+        for item in &lost {
+            if item.attainability.tradeable {
+                gp_lost += u64::from(item.price.market.unwrap_or(item.price.store));
+            } else {
+                reclaim_cost += u64::from(item.price.reclaim.unwrap_or(0));
+            }
+        }
+
+        Self {
+            kept: items,
+            lost,
+            gp_lost,
+            reclaim_cost,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EquipSlot, Price};
+
+    fn item(name: &str, equip_slot: EquipSlot, tradeable: bool, price: Price) -> Item {
+        Item::test(name, equip_slot)
+            .with_tradeable(tradeable)
+            .with_price(price)
+    }
+
+    fn market(price: u32) -> Price {
+        Price {
+            market: Some(price),
+            ..Price::default()
+        }
+    }
+
+    #[test]
+    fn kept_count() {
+        assert_eq!(Risk::kept_count(false, false), 3);
+        assert_eq!(Risk::kept_count(false, true), 4);
+        assert_eq!(Risk::kept_count(true, false), 0);
+        assert_eq!(Risk::kept_count(true, true), 1);
+    }
+
+    #[test]
+    fn risk() {
+        let whip = item("Abyssal whip", EquipSlot::Weapon, true, market(1_500_000));
+        let helm = item("Helm of neitiznot", EquipSlot::Head, true, market(50_000));
+        let boots = item("Climbing boots", EquipSlot::Feet, true, market(10_000));
+        let amulet = item("Amulet of glory", EquipSlot::Neck, true, market(12_000));
+        let cape = item(
+            "Fire cape",
+            EquipSlot::Cape,
+            false,
+            Price {
+                reclaim: Some(50_000),
+                ..Price::default()
+            },
+        );
+
+        let mut loadout = Loadout::new();
+        for item in [&whip, &helm, &boots, &amulet, &cape] {
+            loadout.equip(item);
+        }
+
+        let risk = Risk::new(&loadout, false, false);
+        let names = |items: &[&Item]| items.iter().map(|i| i.name.clone()).collect::<Vec<_>>();
+        assert_eq!(risk.kept.len(), 3);
+        assert_eq!(risk.kept[0].name, "Abyssal whip");
+        assert_eq!(names(&risk.lost), ["Amulet of glory", "Climbing boots"]);
+        assert_eq!(risk.gp_lost, 22_000);
+        assert_eq!(risk.reclaim_cost, 0);
+
+        let risk = Risk::new(&loadout, true, false);
+        assert!(risk.kept.is_empty());
+        assert_eq!(risk.gp_lost, 1_572_000);
+        assert_eq!(risk.reclaim_cost, 50_000);
+
+        let risk = Risk::new(&loadout, true, true);
+        assert_eq!(names(&risk.kept), ["Abyssal whip"]);
+        assert_eq!(risk.gp_lost, 72_000);
+    }
+}
@@ -1,29 +1,63 @@
 //! Datastructures for representing the applicaton state in [`Model`].
 
+pub mod context;
 pub mod filter;
+pub mod optimizer;
 #[cfg(feature = "trailblazer")]
 pub mod region_filter;
+pub mod risk;
 pub mod sorting;
+pub mod url_state;
 
-use data::{Database, EquipSlot, Item};
+use std::collections::BTreeSet;
+
+use anyhow::anyhow;
+use data::{
+    CombatStats, Context, Database, EquipSlot, Item, Loadout, Manifest, MonsterDatabase, Optimizer,
+    Prayer, RequirementType, MANIFEST_FILE,
+};
+use enum_iterator::IntoEnumIterator;
 use lzma_rs::xz_decompress;
 use seed::prelude::*;
 use web_sys::RequestCache;
 
-use filter::Filter;
+use context::ContextMsg;
+use filter::{Filter, FilterMsg};
+use optimizer::{OptimizerMsg, OptimizerResult, OptimizerSettings, RESULT_COUNT};
 #[cfg(feature = "trailblazer")]
 use region_filter::{RegionFilter, TrailblazerMsg};
-use sorting::{Msg as SortingMsg, Sorting};
+use risk::{RiskMsg, RiskSettings};
+use sorting::{CustomSortings, Msg as SortingMsg, Sorting};
+use url_state::UrlState;
+
+const PINNED_STORAGE_KEY: &str = "pinned";
 
 /// The application state.
 #[derive(Default)]
 pub struct Model {
     data: Option<Database>,
+    /// Sorted item indices of each slot, see [`Sorting::order`].
+    order: Vec<Vec<usize>>,
+    /// The indices of `order` kept by the filters.
+    visible: Vec<Vec<usize>>,
+    error: Option<String>,
+    monsters: Option<MonsterDatabase>,
+    monster_error: Option<String>,
     sorting: Sorting,
+    /// User-defined sortings
+    pub custom_sortings: CustomSortings,
     /// Slot currently shown by list view.
     pub list: Option<EquipSlot>,
     /// Miscellanious filtering
     pub filter: Filter,
+    /// Situation of the fight for conditional item bonuses
+    pub context: Context,
+    /// Ids of items which are always part of the loadout
+    pub pinned: Vec<u32>,
+    /// Search for the loadouts with the highest damage per second
+    pub optimizer: OptimizerSettings,
+    /// Items kept on death in the wilderness
+    pub risk: RiskSettings,
     /// Item filtering based on trailblazer regions
     #[cfg(feature = "trailblazer")]
     pub trailblazer: RegionFilter,
@@ -34,9 +68,19 @@ impl Model {
     fn new() -> Self {
         Self {
             data: None,
+            order: Vec::new(),
+            visible: Vec::new(),
+            error: None,
+            monsters: None,
+            monster_error: None,
             sorting: Sorting::new(),
+            custom_sortings: CustomSortings::new(),
             list: None,
             filter: Filter::new(),
+            context: context::load(),
+            pinned: LocalStorage::get(PINNED_STORAGE_KEY).unwrap_or_default(),
+            optimizer: OptimizerSettings::new(),
+            risk: RiskSettings::new(),
         }
     }
 
@@ -44,9 +88,19 @@ impl Model {
     fn new() -> Self {
         Self {
             data: None,
+            order: Vec::new(),
+            visible: Vec::new(),
+            error: None,
+            monsters: None,
+            monster_error: None,
             sorting: Sorting::new(),
+            custom_sortings: CustomSortings::new(),
             list: None,
             filter: Filter::new(),
+            context: context::load(),
+            pinned: LocalStorage::get(PINNED_STORAGE_KEY).unwrap_or_default(),
+            optimizer: OptimizerSettings::new(),
+            risk: RiskSettings::new(),
             trailblazer: RegionFilter::new(),
         }
     }
@@ -57,6 +111,40 @@ impl Model {
         self.data.is_none()
     }
 
+    /// Returns the current sorting settings.
+    #[must_use]
+    pub fn sorting(&self) -> &Sorting {
+        &self.sorting
+    }
+
+    /// Returns the reason why loading the item data failed, if it did.
+    #[must_use]
+    pub fn load_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    /// Returns the monster data, if it has been loaded.
+    #[must_use]
+    pub fn monsters(&self) -> Option<&MonsterDatabase> {
+        self.monsters.as_ref()
+    }
+
+    /// Returns the reason why loading the monster data failed, if it did.
+    #[must_use]
+    pub fn monster_error(&self) -> Option<&str> {
+        self.monster_error.as_deref()
+    }
+
+    /// Get an item by id.
+    ///
+    /// # Panics
+    ///
+    /// When the data is not available.
+    #[must_use]
+    pub fn get_by_id(&self, id: u32) -> Option<&Item> {
+        self.data.as_ref().unwrap().get_by_id(id)
+    }
+
     /// Get item at `index` in `slot`. Filters and sorting will be applied.
     ///
     /// Panics if [`is_loading()`] returns `true`.
@@ -65,16 +153,137 @@ impl Model {
         self.iter(slot).nth(idx)
     }
 
+    /// Get the best item for each slot, choosing between two handed items and the combination
+    /// of weapon and shield. Pinned items replace the best item of their slot. With a budget,
+    /// other items are replaced by cheaper ones until the loadout fits the budget.
+    ///
+    /// # Panics
+    ///
+    /// When the data is not available.
+    #[must_use]
+    pub fn loadout(&self) -> Loadout<'_> {
+        let compare = |a: &CombatStats, b: &CombatStats| self.sorting.compare_stats(a, b);
+        let mut loadout = Loadout::best(|slot| self.iter(slot), compare, &self.context);
+        for &id in &self.pinned {
+            if let Some(item) = self.get_by_id(id) {
+                loadout.equip(item);
+            }
+        }
+        if let Some(budget) = self.filter.budget() {
+            loadout.fit_budget(
+                budget,
+                |slot| self.iter(slot),
+                compare,
+                &self.context,
+                |item| self.is_pinned(item.id),
+            );
+        }
+        loadout
+    }
+
+    /// Names of all quests and diary regions required by any item.
+    ///
+    /// # Panics
+    ///
+    /// When the data is not available.
+    #[must_use]
+    pub fn unlock_names(&self) -> (BTreeSet<&str>, BTreeSet<&str>) {
+        let mut quests = BTreeSet::new();
+        let mut diaries = BTreeSet::new();
+        for requirement in self
+            .data
+            .as_ref()
+            .unwrap()
+            .iter()
+            .flat_map(|item| &item.requirements)
+        {
+            match &requirement.requirement {
+                RequirementType::Quest(quest) => {
+                    quests.insert(quest.as_str());
+                }
+                RequirementType::Diary(region) => {
+                    diaries.insert(region.as_str());
+                }
+                _ => {}
+            }
+        }
+        (quests, diaries)
+    }
+
+    /// Returns `true` if the item with `id` is pinned to the loadout.
+    #[must_use]
+    pub fn is_pinned(&self, id: u32) -> bool {
+        self.pinned.contains(&id)
+    }
+
+    fn toggle_pin(&mut self, id: u32) {
+        if let Some(idx) = self.pinned.iter().position(|&p| p == id) {
+            self.pinned.remove(idx);
+        } else {
+            self.pinned.push(id);
+        }
+        LocalStorage::insert(PINNED_STORAGE_KEY, &self.pinned).unwrap();
+    }
+
     /// Get an iterator for the items in `slot`.
     ///
     /// # Panics
     ///
     /// When the data is not available.
     pub fn iter(&self, slot: EquipSlot) -> impl Iterator<Item = &Item> {
-        self.data.as_ref().unwrap()[slot]
-            .iter()
-            .filter(move |i| self.filter(i))
-            .take_while(move |i| self.sorting.above_neutral(i))
+        let items = &self.data.as_ref().unwrap()[slot];
+        self.visible[slot as usize].iter().map(move |&i| &items[i])
+    }
+
+    /// Search the loadouts with the highest damage per second against the selected monster.
+    ///
+    /// All items kept by the filters are considered, regardless of the sorting.
+    ///
+    /// # Panics
+    ///
+    /// When the data is not available.
+    pub fn optimize(&mut self) {
+        self.optimizer.results = self.optimized();
+    }
+
+    fn optimized(&self) -> Vec<OptimizerResult> {
+        let data = self.data.as_ref().unwrap();
+        let monster = self
+            .monsters
+            .as_ref()
+            .and_then(|m| m.find_by_name(&self.optimizer.monster));
+        monster.map_or_else(Vec::new, |monster| {
+            let mut target = monster.target();
+            target.context.slayer_task = self.context.slayer_task;
+            target.context.wilderness = self.context.wilderness;
+
+            // Stronger prayers are listed later, but should be preferred
+            let mut prayers: Vec<_> = Prayer::into_enum_iter()
+                .filter(|p| self.optimizer.prayers.contains(p))
+                .collect();
+            prayers.reverse();
+
+            let optimizer = Optimizer {
+                levels: self.filter.levels(),
+                prayers,
+                spell_max_hit: self.optimizer.spell_max_hit,
+                current_hitpoints: self.optimizer.current_hitpoints,
+                budget: self.filter.budget(),
+                target: &target,
+            };
+            optimizer
+                .optimize(
+                    |slot| data[slot].iter().filter(move |i| self.filter(i)),
+                    RESULT_COUNT,
+                )
+                .into_iter()
+                .map(|r| OptimizerResult {
+                    items: r.loadout.iter().map(|i| i.id).collect(),
+                    option: r.option.name.clone(),
+                    damage: r.damage,
+                })
+                .collect()
+        })
     }
 
     #[cfg(not(feature = "trailblazer"))]
@@ -87,35 +296,171 @@ impl Model {
         self.filter.keep(item) && self.trailblazer.keep(item)
     }
 
+    /// Sort the items of each slot. Needs to be called when sorting or context change.
     fn sort(&mut self) {
-        let mut data = self.data.take().unwrap();
-        data.sort(|a, b| self.sorting.ordering(a, b));
-        self.data = Some(data);
+        let data = self.data.as_ref().unwrap();
+        self.order = EquipSlot::into_enum_iter()
+            .map(|slot| self.sorting.order(&data[slot], &self.context))
+            .collect();
+        self.refilter();
+    }
+
+    /// Apply the filters to the sorted items. Needs to be called when filters change.
+    fn refilter(&mut self) {
+        let Some(data) = self.data.as_ref() else {
+            return;
+        };
+        self.visible = EquipSlot::into_enum_iter()
+            .zip(&self.order)
+            .map(|(slot, order)| {
+                let items = &data[slot];
+                order
+                    .iter()
+                    .copied()
+                    .filter(|&i| self.filter(&items[i]))
+                    .collect()
+            })
+            .collect();
+    }
+}
+
+/// Initialize the model and start item and monster data loading process.
+///
+/// Settings encoded in `url` take precedence over the stored settings.
+#[allow(clippy::needless_pass_by_value)] // Signature required by seed
+pub fn init(url: Url, orders: &mut impl Orders<Msg>) -> Model {
+    orders.subscribe(Msg::UrlChanged);
+    orders.perform_cmd(fetch_data(false));
+    orders.perform_cmd(fetch_monsters(false));
+
+    let mut model = Model::new();
+    if let Some(state) = UrlState::from_url(&url) {
+        state.apply_to(&mut model);
+    }
+    model
+}
+
+async fn fetch_data(cache_busted: bool) -> Msg {
+    match load_data(cache_busted).await {
+        Ok(data) => Msg::DataLoaded(data.into()),
+        Err(e) => Msg::DataFailed {
+            error: e.to_string(),
+            cache_busted,
+        },
     }
 }
 
-/// Initialize the model and start item data loading process.
-pub fn init(_: Url, orders: &mut impl Orders<Msg>) -> Model {
-    orders.perform_cmd(async { Msg::DataLoaded(load_data().await.into()) });
-    Model::new()
+async fn fetch_monsters(cache_busted: bool) -> Msg {
+    match load_monsters(cache_busted).await {
+        Ok(monsters) => Msg::MonstersLoaded(monsters.into()),
+        Err(e) => Msg::MonstersFailed {
+            error: e.to_string(),
+            cache_busted,
+        },
+    }
+}
+
+async fn load_data(cache_busted: bool) -> anyhow::Result<Database> {
+    let hash = load_manifest().await.and_then(|manifest| manifest.items);
+    let hash = hash.map(|file| file.hash);
+    let bytes = download("items.bin.xz", "item", hash, cache_busted).await?;
+    let (_, database) = Database::decode(&bytes)?;
+    Ok(database)
 }
 
-async fn load_data() -> Database {
-    let request = Request::new("items.bin.xz").cache(RequestCache::NoCache);
-    let response = request.fetch().await.unwrap();
-    let bytes = response.bytes().await.unwrap();
+async fn load_monsters(cache_busted: bool) -> anyhow::Result<MonsterDatabase> {
+    let hash = load_manifest().await.and_then(|manifest| manifest.monsters);
+    let hash = hash.map(|file| file.hash);
+    let bytes = download("monsters.bin.xz", "monster", hash, cache_busted).await?;
+    let (_, database) = MonsterDatabase::decode(&bytes)?;
+    Ok(database)
+}
+
+/// Download the build manifest, which is missing in older deployments.
+async fn load_manifest() -> Option<Manifest> {
+    let response = Request::new(MANIFEST_FILE)
+        .cache(RequestCache::NoCache)
+        .fetch()
+        .await
+        .and_then(Response::check_status)
+        .ok()?;
+    let json = response.text().await.ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Download and decompress `file`. `kind` describes the data in error messages.
+///
+/// With the content `hash` of the file from the manifest, the file is cached by its hash, so it is
+/// only downloaded again after it changed.
+async fn download(
+    file: &str,
+    kind: &str,
+    hash: Option<String>,
+    cache_busted: bool,
+) -> anyhow::Result<Vec<u8>> {
+    let request = if cache_busted {
+        // Unique URL to get past any cache holding data from an older deployment
+        let url = format!("{}?t={}", file, js_sys::Date::now());
+        Request::new(url).cache(RequestCache::Reload)
+    } else if let Some(hash) = hash {
+        Request::new(format!("{}?v={}", file, hash))
+    } else {
+        Request::new(file).cache(RequestCache::NoCache)
+    };
+
+    let fetch_error = |e| anyhow!("failed to download {} data: {:?}", kind, e);
+    let response = request
+        .fetch()
+        .await
+        .and_then(Response::check_status)
+        .map_err(fetch_error)?;
+    let bytes = response.bytes().await.map_err(fetch_error)?;
 
     let mut decompressed = Vec::new();
-    xz_decompress(&mut bytes.as_slice(), &mut decompressed).unwrap();
-    bincode::deserialize(&decompressed).unwrap()
+    xz_decompress(&mut bytes.as_slice(), &mut decompressed)
+        .map_err(|e| anyhow!("failed to decompress {} data: {:?}", kind, e))?;
+
+    Ok(decompressed)
 }
 
 /// Possible events.
 pub enum Msg {
     /// Item database has finished downloading.
     DataLoaded(Box<Database>),
+    /// Item database could not be loaded.
+    DataFailed {
+        /// Description of the problem.
+        error: String,
+        /// Whether the request already bypassed the browser cache.
+        cache_busted: bool,
+    },
+    /// Monster database has finished downloading.
+    MonstersLoaded(Box<MonsterDatabase>),
+    /// Monster database could not be loaded.
+    MonstersFailed {
+        /// Description of the problem.
+        error: String,
+        /// Whether the request already bypassed the browser cache.
+        cache_busted: bool,
+    },
+    /// The URL was changed by navigating back or forward.
+    UrlChanged(subs::UrlChanged),
     /// Change the current slot of the list view.
     ChangeList(EquipSlot),
+    /// Pin / unpin the item with the given id to the loadout.
+    TogglePin(u32),
+    /// Message to change general filtering.
+    Filter(FilterMsg),
+    /// Message to change the situation of the fight.
+    ///
+    /// Will trigger a sort afterwards.
+    Context(ContextMsg),
+    /// Message to change the optimizer settings.
+    Optimizer(OptimizerMsg),
+    /// Search the loadouts with the highest damage per second.
+    Optimize,
+    /// Message to change the settings for items kept on death.
+    Risk(RiskMsg),
     /// Message to change region-based filtering.
     #[cfg(feature = "trailblazer")]
     Trailblazer(TrailblazerMsg),
@@ -126,19 +471,89 @@ pub enum Msg {
 }
 
 /// Reacts to events.
+///
+/// Changed settings are added to the browser history, see [`url_state`]. Changes made while typing
+/// replace the current entry instead.
 pub fn update(msg: Msg, model: &mut Model, orders: &mut impl Orders<Msg>) {
+    let typed = match &msg {
+        Msg::Filter(msg) => msg.is_typed(),
+        Msg::Sorting(msg) => msg.is_typed(),
+        _ => false,
+    };
+
     match msg {
         Msg::DataLoaded(data) => {
             debug_assert!(model.data.is_none());
             model.data = Some(*data);
+            model.error = None;
             model.sort();
         }
+        Msg::DataFailed {
+            error,
+            cache_busted,
+        } => {
+            if cache_busted {
+                model.error = Some(error);
+            } else {
+                // A stale cached copy is the most likely cause, so try again with a fresh one.
+                orders.perform_cmd(fetch_data(true));
+            }
+        }
+        Msg::MonstersLoaded(monsters) => {
+            model.monsters = Some(*monsters);
+            model.monster_error = None;
+        }
+        Msg::MonstersFailed {
+            error,
+            cache_busted,
+        } => {
+            if cache_busted {
+                model.monster_error = Some(error);
+            } else {
+                orders.perform_cmd(fetch_monsters(true));
+            }
+        }
+        Msg::UrlChanged(subs::UrlChanged(url)) => {
+            if let Some(state) = UrlState::from_url(&url) {
+                model.optimizer.results.clear();
+                if state.apply_to(model) && model.data.is_some() {
+                    model.sort();
+                } else {
+                    model.refilter();
+                }
+            }
+            // Restoring a previous view must not add a new history entry
+            return;
+        }
         Msg::ChangeList(slot) => model.list = Some(slot),
+        Msg::TogglePin(id) => model.toggle_pin(id),
+        Msg::Filter(msg) => {
+            filter::update(msg, &mut model.filter, orders);
+            model.optimizer.results.clear();
+            model.refilter();
+        }
+        Msg::Context(msg) => {
+            context::update(msg, &mut model.context, orders);
+            model.optimizer.results.clear();
+            model.sort();
+        }
+        Msg::Optimizer(msg) => optimizer::update(msg, &mut model.optimizer, orders),
+        Msg::Optimize => model.optimize(),
+        Msg::Risk(msg) => risk::update(msg, &mut model.risk, orders),
         #[cfg(feature = "trailblazer")]
-        Msg::Trailblazer(msg) => region_filter::update(msg, &mut model.trailblazer, orders),
+        Msg::Trailblazer(msg) => {
+            region_filter::update(msg, &mut model.trailblazer, orders);
+            model.refilter();
+        }
         Msg::Sorting(msg) => {
-            sorting::update(&msg, &mut model.sorting, orders);
+            sorting::update(&msg, &mut model.sorting, &mut model.custom_sortings, orders);
             model.sort();
         }
     }
+
+    if typed {
+        url_state::replace(model);
+    } else {
+        url_state::push(model);
+    }
 }
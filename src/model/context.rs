@@ -0,0 +1,42 @@
+//! Situation of the fight, determines which conditional item bonuses apply.
+
+use data::{Context, MonsterAttribute};
+use seed::prelude::{LocalStorage, Orders, WebStorage};
+
+use super::Msg as SuperMsg;
+
+const STORAGE_KEY: &str = "context";
+
+/// Load the context from web storage or create a default context as fallback.
+#[must_use]
+pub fn load() -> Context {
+    LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+}
+
+/// Messages to manipulate the context. Each message toggles the respective setting.
+#[derive(Clone, Copy)]
+pub enum ContextMsg {
+    /// Toggle whether the target is the current slayer task.
+    SlayerTask,
+    /// Toggle whether the fight takes place in the wilderness.
+    Wilderness,
+    /// Toggle an attribute of the target.
+    Attribute(MonsterAttribute),
+}
+
+/// Change the context based on [`ContextMsg`].
+pub fn update(msg: ContextMsg, context: &mut Context, _orders: &mut impl Orders<SuperMsg>) {
+    match msg {
+        ContextMsg::SlayerTask => context.slayer_task = !context.slayer_task,
+        ContextMsg::Wilderness => context.wilderness = !context.wilderness,
+        ContextMsg::Attribute(attribute) => {
+            if let Some(idx) = context.attributes.iter().position(|&a| a == attribute) {
+                context.attributes.remove(idx);
+            } else {
+                context.attributes.push(attribute);
+            }
+        }
+    }
+
+    LocalStorage::insert(STORAGE_KEY, context).unwrap();
+}
@@ -1,17 +1,47 @@
 //! Miscellanious filters.
 
-use data::Item;
-use seed::prelude::{LocalStorage, WebStorage};
+use std::collections::HashMap;
+
+use data::{
+    normalize_name, Clue, Item, PlayerLevels, Requirement, RequirementType, Skill, Unlocks,
+};
+use seed::prelude::{LocalStorage, Orders, WebStorage};
 use serde::{Deserialize, Serialize};
 
+use super::Msg as SuperMsg;
+
 const STORAGE_KEY: &str = "filter";
 
 /// Stores current settings for general filtering.
 ///
 /// Uses individual named fields to allow manual inspection / editing of local storage.
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct Filter {
+    #[serde(default)]
     members: bool,
+    #[serde(default)]
+    usable: bool,
+    #[serde(default, skip_serializing_if = "is_default")]
+    levels: PlayerLevels,
+    #[serde(default, skip_serializing_if = "is_default")]
+    unlocks: Unlocks,
+    #[serde(default)]
+    tradeable: bool,
+    #[serde(default)]
+    mega_rares: bool,
+    #[serde(default)]
+    max_clue: Option<Clue>,
+    #[serde(default)]
+    search: String,
+    #[serde(default)]
+    ironman: bool,
+    #[serde(default)]
+    max_price: Option<u32>,
+    #[serde(default)]
+    budget: Option<u64>,
+    #[serde(default)]
+    requirement_limits: HashMap<Skill, u8>,
 }
 
 impl Filter {
@@ -21,9 +51,235 @@ impl Filter {
         LocalStorage::get(STORAGE_KEY).unwrap_or_default()
     }
 
+    fn updated(&self) {
+        LocalStorage::insert(STORAGE_KEY, self).unwrap();
+    }
+
+    /// A copy without the player's levels and unlocks, which describe the player and not the
+    /// view. Used for sharing the filter.
+    #[must_use]
+    pub fn without_player(&self) -> Self {
+        Self {
+            levels: PlayerLevels::default(),
+            unlocks: Unlocks::default(),
+            ..self.clone()
+        }
+    }
+
+    /// Replace the player's levels and unlocks with those of `other`.
+    #[must_use]
+    pub fn with_player_of(self, other: &Self) -> Self {
+        Self {
+            levels: other.levels.clone(),
+            unlocks: other.unlocks.clone(),
+            ..self
+        }
+    }
+
+    /// Returns `true` if members items are excluded.
+    #[must_use]
+    pub fn f2p(&self) -> bool {
+        self.members
+    }
+
+    /// Returns `true` if untradeable items are excluded.
+    #[must_use]
+    pub fn tradeable(&self) -> bool {
+        self.tradeable
+    }
+
+    /// Returns `true` if mega-rare clue rewards are excluded.
+    #[must_use]
+    pub fn mega_rares(&self) -> bool {
+        self.mega_rares
+    }
+
+    /// Returns the highest clue tier of items which are kept, if clue rewards are limited.
+    #[must_use]
+    pub fn max_clue(&self) -> Option<Clue> {
+        self.max_clue
+    }
+
+    /// Returns `true` if items an iron man can not obtain with the current levels are excluded.
+    #[must_use]
+    pub fn ironman(&self) -> bool {
+        self.ironman
+    }
+
+    /// Returns the highest Grand Exchange price of items which are kept, if prices are limited.
+    #[must_use]
+    pub fn max_price(&self) -> Option<u32> {
+        self.max_price
+    }
+
+    /// Returns the highest total Grand Exchange price of a loadout, if the budget is limited.
+    #[must_use]
+    pub fn budget(&self) -> Option<u64> {
+        self.budget
+    }
+
+    /// Returns the highest level requirement in `skill` of items which are kept, if limited.
+    #[must_use]
+    pub fn requirement_limit(&self, skill: Skill) -> Option<u8> {
+        self.requirement_limits.get(&skill).copied()
+    }
+
+    /// Returns the text item names have to contain.
+    #[must_use]
+    pub fn search(&self) -> &str {
+        &self.search
+    }
+
+    /// Returns `true` if only items usable with the current levels are shown.
+    #[must_use]
+    pub fn usable(&self) -> bool {
+        self.usable
+    }
+
+    /// Returns a reference to the player's levels.
+    #[must_use]
+    pub fn levels(&self) -> &PlayerLevels {
+        &self.levels
+    }
+
+    /// Returns a reference to the player's completed quests, diaries and combat achievements.
+    #[must_use]
+    pub fn unlocks(&self) -> &Unlocks {
+        &self.unlocks
+    }
+
     /// Returns `false` if the item is excluded by the current filter settings.
     #[must_use]
     pub fn keep(&self, item: &Item) -> bool {
-        !(self.members && item.members)
+        if self.members && item.members {
+            return false;
+        }
+
+        if self.usable && !item.usable_by(&self.levels, &self.unlocks) {
+            return false;
+        }
+
+        if self.tradeable && !item.attainability.tradeable {
+            return false;
+        }
+
+        let exceeds_limit = |r: &Requirement| match r.requirement {
+            RequirementType::Skill(skill) => self
+                .requirement_limit(skill)
+                .is_some_and(|max| r.level > max),
+            _ => false,
+        };
+        if item.requirements.iter().any(exceeds_limit) {
+            return false;
+        }
+
+        if let (Some(max), Some(price)) = (self.max_price, item.price.market) {
+            if price > max {
+                return false;
+            }
+        }
+
+        // Items exceeding the budget on their own can not be part of any loadout
+        if let (Some(budget), Some(price)) = (self.budget, item.price.market) {
+            if u64::from(price) > budget {
+                return false;
+            }
+        }
+
+        if self.ironman && !item.attainability.ironman(&self.levels, &self.unlocks) {
+            return false;
+        }
+
+        if let Some(clue) = item.attainability.clue {
+            if self.mega_rares && clue.is_mega_rare() {
+                return false;
+            }
+            if self.max_clue.is_some_and(|max| clue.tier() > max) {
+                return false;
+            }
+        }
+
+        self.search.is_empty() || normalize_name(&item.name).contains(&normalize_name(&self.search))
+    }
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// Messages to manipulate general filters.
+pub enum FilterMsg {
+    /// Enable / disable filtering of members items.
+    ToggleF2p,
+    /// Enable / disable filtering of untradeable items.
+    ToggleTradeable,
+    /// Enable / disable filtering of mega-rare clue rewards.
+    ToggleMegaRares,
+    /// Enable / disable filtering of items an iron man can not obtain.
+    ToggleIronman,
+    /// Change the highest clue tier of kept clue rewards, `None` keeps all tiers.
+    SetMaxClue(Option<Clue>),
+    /// Change the text item names have to contain.
+    SetSearch(String),
+    /// Change the highest Grand Exchange price of kept items, `None` keeps all items.
+    SetMaxPrice(Option<u32>),
+    /// Change the highest total Grand Exchange price of a loadout, `None` for no limit.
+    SetBudget(Option<u64>),
+    /// Change the highest level requirement in a skill of kept items, `None` for no limit.
+    SetRequirementLimit(Skill, Option<u8>),
+    /// Enable / disable filtering of items the player can not equip.
+    ToggleUsable,
+    /// Change the player's level in a skill.
+    SetLevel(Skill, u8),
+    /// Mark the named quest as completed / not completed.
+    ToggleQuest(String),
+    /// Change the highest completed diary tier of the named region.
+    SetDiary(String, u8),
+    /// Change the highest completed combat achievement tier.
+    SetCombatAchievements(u8),
+}
+
+impl FilterMsg {
+    /// Returns `true` if the message is sent on every keystroke of a text or number input.
+    #[must_use]
+    pub fn is_typed(&self) -> bool {
+        matches!(
+            self,
+            Self::SetSearch(_)
+                | Self::SetMaxPrice(_)
+                | Self::SetBudget(_)
+                | Self::SetRequirementLimit(..)
+                | Self::SetLevel(..)
+        )
     }
 }
+
+/// Change filters based on [`FilterMsg`].
+pub fn update(msg: FilterMsg, filter: &mut Filter, _orders: &mut impl Orders<SuperMsg>) {
+    match msg {
+        FilterMsg::ToggleF2p => filter.members = !filter.members,
+        FilterMsg::ToggleTradeable => filter.tradeable = !filter.tradeable,
+        FilterMsg::ToggleMegaRares => filter.mega_rares = !filter.mega_rares,
+        FilterMsg::ToggleIronman => filter.ironman = !filter.ironman,
+        FilterMsg::SetMaxClue(clue) => filter.max_clue = clue.map(Clue::tier),
+        FilterMsg::SetSearch(search) => filter.search = search,
+        FilterMsg::SetMaxPrice(price) => filter.max_price = price,
+        FilterMsg::SetBudget(budget) => filter.budget = budget,
+        FilterMsg::SetRequirementLimit(skill, Some(level)) => {
+            filter.requirement_limits.insert(skill, level);
+        }
+        FilterMsg::SetRequirementLimit(skill, None) => {
+            filter.requirement_limits.remove(&skill);
+        }
+        FilterMsg::ToggleUsable => filter.usable = !filter.usable,
+        FilterMsg::SetLevel(skill, level) => filter.levels.set(skill, level),
+        FilterMsg::ToggleQuest(quest) => {
+            let completed = !filter.unlocks.quest(&quest);
+            filter.unlocks.set_quest(&quest, completed);
+        }
+        FilterMsg::SetDiary(region, tier) => filter.unlocks.set_diary(&region, tier),
+        FilterMsg::SetCombatAchievements(tier) => filter.unlocks.set_combat_achievements(tier),
+    }
+
+    filter.updated();
+}
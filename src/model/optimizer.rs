@@ -0,0 +1,86 @@
+//! Settings and results of the search for the loadouts with the highest damage per second.
+
+use data::{Damage, Prayer};
+use seed::prelude::{LocalStorage, Orders, WebStorage};
+use serde::{Deserialize, Serialize};
+
+use super::Msg as SuperMsg;
+
+const STORAGE_KEY: &str = "optimizer";
+
+/// Number of loadouts shown as result.
+pub const RESULT_COUNT: usize = 5;
+
+/// Stores the settings of the optimizer and the results of the last search.
+///
+/// Uses individual named fields to allow manual inspection / editing of local storage.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct OptimizerSettings {
+    /// Name of the monster to fight.
+    pub monster: String,
+    /// Prayers the player can use.
+    pub prayers: Vec<Prayer>,
+    /// Base max hit of the spell being cast, `0` to ignore magic.
+    pub spell_max_hit: u16,
+    /// Current hitpoints of the player, `None` for full hitpoints.
+    #[serde(default)]
+    pub current_hitpoints: Option<u8>,
+    /// Results of the last search.
+    #[serde(skip)]
+    pub results: Vec<OptimizerResult>,
+}
+
+impl OptimizerSettings {
+    /// Create a new instance loaded from web storage or created with default values as fallback.
+    #[must_use]
+    pub fn new() -> Self {
+        LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+    }
+}
+
+/// A loadout found by the optimizer. Items are referenced by id, since the item data is
+/// re-sorted while the results are shown.
+#[derive(Debug, Clone)]
+pub struct OptimizerResult {
+    /// Ids of the equipped items.
+    pub items: Vec<u32>,
+    /// Name of the combat option to attack with.
+    pub option: String,
+    /// Outcome of attacking the monster.
+    pub damage: Damage,
+}
+
+/// Messages to change the optimizer settings.
+pub enum OptimizerMsg {
+    /// Change the monster to fight.
+    SetMonster(String),
+    /// Enable / disable a prayer.
+    TogglePrayer(Prayer),
+    /// Change the base max hit of the spell being cast.
+    SetSpellMaxHit(u16),
+    /// Change the current hitpoints, `None` for full hitpoints.
+    SetCurrentHitpoints(Option<u8>),
+}
+
+/// Change the settings based on [`OptimizerMsg`]. Previous results are discarded.
+pub fn update(
+    msg: OptimizerMsg,
+    settings: &mut OptimizerSettings,
+    _orders: &mut impl Orders<SuperMsg>,
+) {
+    match msg {
+        OptimizerMsg::SetMonster(name) => settings.monster = name,
+        OptimizerMsg::TogglePrayer(prayer) => {
+            if let Some(idx) = settings.prayers.iter().position(|&p| p == prayer) {
+                settings.prayers.remove(idx);
+            } else {
+                settings.prayers.push(prayer);
+            }
+        }
+        OptimizerMsg::SetSpellMaxHit(max_hit) => settings.spell_max_hit = max_hit,
+        OptimizerMsg::SetCurrentHitpoints(hitpoints) => settings.current_hitpoints = hitpoints,
+    }
+
+    settings.results.clear();
+    LocalStorage::insert(STORAGE_KEY, settings).unwrap();
+}
@@ -10,7 +10,7 @@ use super::Msg;
 const STORAGE_KEY: &str = "region-filter";
 
 /// Stores current settings for region filtering.
-#[derive(Debug, Default, Deserialize, Serialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct RegionFilter {
     enabled: bool,
     filter: RegionCombination,
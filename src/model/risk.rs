@@ -0,0 +1,48 @@
+//! Settings for the items kept on death in the wilderness.
+
+use seed::prelude::{LocalStorage, Orders, WebStorage};
+use serde::{Deserialize, Serialize};
+
+use super::Msg as SuperMsg;
+
+const STORAGE_KEY: &str = "risk";
+
+/// Stores the settings which determine the number of items kept on death.
+///
+/// Uses individual named fields to allow manual inspection / editing of local storage.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct RiskSettings {
+    /// Whether the player is skulled.
+    #[serde(default)]
+    pub skulled: bool,
+    /// Whether the Protect Item prayer is active.
+    #[serde(default)]
+    pub protect_item: bool,
+}
+
+impl RiskSettings {
+    /// Create a new instance loaded from web storage or created with default values as fallback.
+    #[must_use]
+    pub fn new() -> Self {
+        LocalStorage::get(STORAGE_KEY).unwrap_or_default()
+    }
+}
+
+/// Messages to change the risk settings. Each message toggles the respective setting.
+#[derive(Clone, Copy)]
+pub enum RiskMsg {
+    /// Toggle whether the player is skulled.
+    Skulled,
+    /// Toggle whether the Protect Item prayer is active.
+    ProtectItem,
+}
+
+/// Change the settings based on [`RiskMsg`].
+pub fn update(msg: RiskMsg, settings: &mut RiskSettings, _orders: &mut impl Orders<SuperMsg>) {
+    match msg {
+        RiskMsg::Skulled => settings.skulled = !settings.skulled,
+        RiskMsg::ProtectItem => settings.protect_item = !settings.protect_item,
+    }
+
+    LocalStorage::insert(STORAGE_KEY, settings).unwrap();
+}
@@ -2,7 +2,7 @@
 
 use std::cmp::Ordering;
 
-use data::{CombatStats, DamageType, Item};
+use data::{CombatStats, Context, DamageType, Item};
 use enum_iterator::IntoEnumIterator;
 use seed::prelude::{LocalStorage, Orders, WebStorage};
 use serde::{Deserialize, Serialize};
@@ -10,9 +10,10 @@ use serde::{Deserialize, Serialize};
 use super::Msg as SuperMsg;
 
 const STORAGE_KEY: &str = "sorting";
+const CUSTOM_STORAGE_KEY: &str = "custom-sortings";
 
 /// Fragments for building a sorting method.
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, IntoEnumIterator)]
 pub enum Fragment {
     /// Average of melee attack bonuses
     MeleeAttackAvg,
@@ -30,6 +31,10 @@ pub enum Fragment {
     MagicDamage,
     /// Sort by prayer bonus
     Prayer,
+    /// Grand Exchange price per stat point, cheapest first. Stat points are the weighted sum of
+    /// the other fragments, or of all stats if there are none. Items without a price or without
+    /// stat points are sorted last.
+    GpPerStatPoint,
 }
 
 impl Fragment {
@@ -43,9 +48,10 @@ impl Fragment {
             Self::RangedAttack => i.attack[DamageType::Ranged],
             Self::DefenceMedian => {
                 let d = &i.defence;
-                let mut stats: Vec<_> = DamageType::into_enum_iter()
-                    .map(|damage_type| d[damage_type])
-                    .collect();
+                let mut stats = [0; DamageType::VARIANT_COUNT];
+                for (stat, damage_type) in stats.iter_mut().zip(DamageType::into_enum_iter()) {
+                    *stat = d[damage_type];
+                }
                 stats.sort_unstable();
                 stats[DamageType::VARIANT_COUNT / 2]
             }
@@ -53,13 +59,18 @@ impl Fragment {
             Self::RangedStrength => i.ranged_strength,
             Self::MagicDamage => i.magic_damage,
             Self::Prayer => i.prayer,
+            Self::GpPerStatPoint => 0,
         }
     }
+}
 
-    fn ordering(self, a: &CombatStats, b: &CombatStats) -> Ordering {
-        let ord = self.get(a).cmp(&self.get(b));
-        ord.reverse()
-    }
+/// Value of [`Fragment::GpPerStatPoint`]: negated coins per point, so cheaper items are higher.
+/// `None` without a price or stat points.
+fn gp_per_stat_point(points: f32, price: Option<u32>) -> Option<f32> {
+    #[allow(clippy::cast_precision_loss)]
+    price
+        .filter(|_| points > 0.0)
+        .map(|price| -(price as f32) / points)
 }
 
 /// Presets for sorting
@@ -101,20 +112,61 @@ const PRAYER_PRESET: &[Fragment] = &[Fragment::Prayer, Fragment::DefenceMedian];
 impl Preset {
     /// Apply this preset to `sorting`.
     pub fn apply_to(self, sorting: &mut Sorting) {
-        sorting.0.clear();
         let list = match self {
             Self::Melee => MELEE_PRESET,
             Self::Magic => MAGIC_PRESET,
             Self::Ranged => RANGED_PRESET,
             Self::Prayer => PRAYER_PRESET,
         };
-        sorting.0.extend_from_slice(list);
+        *sorting = Sorting::lexicographic(list);
     }
 }
 
+/// How the fragments of a [`Sorting`] are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Mode {
+    /// Compare by the first fragment, use later fragments to break ties.
+    Lexicographic,
+    /// Compare by the sum of the fragment values multiplied by their weights.
+    Weighted,
+}
+
+/// A [`Fragment`] with its weight for [`Mode::Weighted`].
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct WeightedFragment {
+    /// The fragment.
+    pub fragment: Fragment,
+    /// Factor applied to the fragment value.
+    pub weight: f32,
+}
+
 /// Stores current settings for item sorting.
-#[derive(Debug, Deserialize, Serialize)]
-pub struct Sorting(Vec<Fragment>);
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(from = "StoredSorting")]
+pub struct Sorting {
+    mode: Mode,
+    fragments: Vec<WeightedFragment>,
+}
+
+/// Stored formats of [`Sorting`], including the plain fragment list of older versions.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredSorting {
+    Current {
+        mode: Mode,
+        fragments: Vec<WeightedFragment>,
+    },
+    Fragments(Vec<Fragment>),
+}
+
+impl From<StoredSorting> for Sorting {
+    fn from(stored: StoredSorting) -> Self {
+        match stored {
+            StoredSorting::Current { mode, fragments } => Self { mode, fragments },
+            StoredSorting::Fragments(fragments) => Self::lexicographic(&fragments),
+        }
+    }
+}
 
 impl Sorting {
     /// Create a new instance loaded from web storage or created with default values as fallback.
@@ -123,58 +175,366 @@ impl Sorting {
         LocalStorage::get(STORAGE_KEY).unwrap_or_default()
     }
 
+    /// Create a lexicographic sorting from `fragments`, each with weight `1.0`.
+    #[must_use]
+    pub fn lexicographic(fragments: &[Fragment]) -> Self {
+        Self {
+            mode: Mode::Lexicographic,
+            fragments: fragments
+                .iter()
+                .map(|&fragment| WeightedFragment {
+                    fragment,
+                    weight: 1.0,
+                })
+                .collect(),
+        }
+    }
+
     fn updated(&self) {
         LocalStorage::insert(STORAGE_KEY, self).unwrap();
     }
 
-    /// Get an ordering between items `a` and `b` based on current settings.
+    /// Returns how the fragments are combined.
+    #[must_use]
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Returns the fragments in order of priority.
+    #[must_use]
+    pub fn fragments(&self) -> &[WeightedFragment] {
+        &self.fragments
+    }
+
+    /// Sort the indices of `items` based on current settings, including the conditional bonuses
+    /// which apply in `context`. Items with stats which are not better than neutral stats are
+    /// removed, prices are not considered for this.
     ///
-    /// This method will always impose alphabetical ordering as a last step.
+    /// The values compared are computed once per item. Alphabetical ordering is always imposed
+    /// as a last step.
     #[must_use]
-    pub fn ordering(&self, a: &Item, b: &Item) -> Ordering {
-        let mut ordering = Ordering::Equal;
+    pub fn order(&self, items: &[Item], context: &Context) -> Vec<usize> {
+        let stats: Vec<_> = items.iter().map(|i| i.effective_stats(context)).collect();
+        let keys: Vec<_> = items
+            .iter()
+            .zip(&stats)
+            .map(|(i, stats)| self.key(stats, i.price.market))
+            .collect();
 
-        for frag in &self.0 {
-            ordering = ordering.then_with(|| frag.ordering(&a.combat_stats, &b.combat_stats));
-        }
+        let mut order: Vec<_> = (0..items.len()).collect();
+        order.sort_unstable_by(|&a, &b| {
+            let (item_a, item_b) = (&items[a], &items[b]);
+            compare_keys(&keys[a], &keys[b])
+                .then_with(|| item_a.attainability.clue.cmp(&item_b.attainability.clue))
+                .then_with(|| item_a.name.cmp(&item_b.name))
+        });
 
-        let ordering = ordering.then_with(|| a.attainability.clue.cmp(&b.attainability.clue));
-        ordering.then_with(|| a.name.cmp(&b.name))
+        // Only items above neutral are kept
+        let stat_fragments = self.stat_fragments();
+        let neutral = self.stat_key(&stat_fragments, &CombatStats::default());
+        order.retain(|&i| {
+            compare_keys(&self.stat_key(&stat_fragments, &stats[i]), &neutral) == Ordering::Less
+        });
+        order
     }
 
-    /// Returns `true` if the item is better than an item with neutral stats under the
-    /// current sorting order.
+    /// Get an ordering between stats `a` and `b` based on current settings. Better stats are
+    /// ordered first. Prices are not known, so [`Fragment::GpPerStatPoint`] is ignored.
     #[must_use]
-    pub fn above_neutral(&self, i: &Item) -> bool {
-        let mut ordering = Ordering::Equal;
+    pub fn compare_stats(&self, a: &CombatStats, b: &CombatStats) -> Ordering {
+        compare_keys(&self.key(a, None), &self.key(b, None))
+    }
+
+    /// The values compared for `stats` of an item with `price`, in order of priority.
+    ///
+    /// In weighted mode, items without a [`Fragment::GpPerStatPoint`] value are sorted after all
+    /// others by a leading value.
+    fn key(&self, stats: &CombatStats, price: Option<u32>) -> Vec<f32> {
+        let gp = || gp_per_stat_point(self.score(stats), price);
+
+        match self.mode {
+            Mode::Lexicographic => self
+                .fragments
+                .iter()
+                .map(|f| match f.fragment {
+                    Fragment::GpPerStatPoint => gp().unwrap_or(f32::NEG_INFINITY),
+                    fragment => f32::from(fragment.get(stats)),
+                })
+                .collect(),
+            Mode::Weighted => {
+                let gp = gp();
+                let sum = self
+                    .fragments
+                    .iter()
+                    .map(|f| match f.fragment {
+                        Fragment::GpPerStatPoint => f.weight * gp.unwrap_or(0.0),
+                        fragment => f.weight * f32::from(fragment.get(stats)),
+                    })
+                    .sum();
+                let priced = self
+                    .fragments
+                    .iter()
+                    .any(|f| f.fragment == Fragment::GpPerStatPoint);
+                if priced {
+                    vec![if gp.is_some() { 1.0 } else { 0.0 }, sum]
+                } else {
+                    vec![sum]
+                }
+            }
+        }
+    }
 
-        let default = CombatStats::default();
-        for frag in &self.0 {
-            ordering = ordering.then_with(|| frag.ordering(&i.combat_stats, &default));
+    /// The fragments without [`Fragment::GpPerStatPoint`]. If there are none, all other
+    /// fragments with weight `1.0`.
+    fn stat_fragments(&self) -> Vec<WeightedFragment> {
+        let fragments: Vec<_> = self
+            .fragments
+            .iter()
+            .copied()
+            .filter(|f| f.fragment != Fragment::GpPerStatPoint)
+            .collect();
+        if !fragments.is_empty() {
+            return fragments;
         }
 
-        ordering == Ordering::Less
+        Fragment::into_enum_iter()
+            .filter(|&f| f != Fragment::GpPerStatPoint)
+            .map(|fragment| WeightedFragment {
+                fragment,
+                weight: 1.0,
+            })
+            .collect()
+    }
+
+    /// The values of `fragments` for `stats`, combined according to the mode.
+    fn stat_key(&self, fragments: &[WeightedFragment], stats: &CombatStats) -> Vec<f32> {
+        let values = fragments
+            .iter()
+            .map(|f| (f.weight, f32::from(f.fragment.get(stats))));
+        match self.mode {
+            Mode::Lexicographic => values.map(|(_, value)| value).collect(),
+            Mode::Weighted => vec![values.map(|(weight, value)| weight * value).sum()],
+        }
+    }
+
+    /// Weighted sum of the stat fragment values of `stats`, see [`Self::stat_fragments`].
+    fn score(&self, stats: &CombatStats) -> f32 {
+        self.stat_fragments()
+            .iter()
+            .map(|f| f.weight * f32::from(f.fragment.get(stats)))
+            .sum()
     }
 }
 
+/// Compare values computed by [`Sorting::key`], higher values are ordered first.
+fn compare_keys(a: &[f32], b: &[f32]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| b.total_cmp(a))
+        .find(|o| o.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
 impl Default for Sorting {
     fn default() -> Self {
-        let mut result = Self(Vec::new());
+        let mut result = Self::lexicographic(&[]);
         Preset::Melee.apply_to(&mut result);
         result
     }
 }
 
+/// A user-defined sorting.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct CustomSorting {
+    /// Name chosen by the user.
+    pub name: String,
+    /// The sorting settings.
+    pub sorting: Sorting,
+}
+
+/// Stores the user-defined sortings.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct CustomSortings(Vec<CustomSorting>);
+
+impl CustomSortings {
+    /// Create a new instance loaded from web storage or created with default values as fallback.
+    #[must_use]
+    pub fn new() -> Self {
+        LocalStorage::get(CUSTOM_STORAGE_KEY).unwrap_or_default()
+    }
+
+    fn updated(&self) {
+        LocalStorage::insert(CUSTOM_STORAGE_KEY, self).unwrap();
+    }
+
+    /// Iterate over the user-defined sortings.
+    pub fn iter(&self) -> impl Iterator<Item = &CustomSorting> {
+        self.0.iter()
+    }
+}
+
 /// Messages to manipulate sorting order
 pub enum Msg {
     /// Apply a preset to the sorting order.
     ApplyPreset(Preset),
+    /// Apply the user-defined sorting at the index.
+    ApplyCustom(usize),
+    /// Save the current sorting under the given name, replacing a sorting with the same name.
+    SaveCustom(String),
+    /// Delete the user-defined sorting at the index.
+    DeleteCustom(usize),
+    /// Change how fragments are combined.
+    SetMode(Mode),
+    /// Add a fragment with the lowest priority.
+    AddFragment(Fragment),
+    /// Remove the fragment at the index.
+    RemoveFragment(usize),
+    /// Swap the fragment at the index with the previous one.
+    MoveFragmentUp(usize),
+    /// Change the weight of the fragment at the index.
+    SetWeight(usize, f32),
+}
+
+impl Msg {
+    /// Returns `true` if the message is sent on every keystroke of a number input.
+    #[must_use]
+    pub fn is_typed(&self) -> bool {
+        matches!(self, Self::SetWeight(..))
+    }
 }
 
 /// Change sorting based on [`SortingMsg`].
-pub fn update(msg: &Msg, sorting: &mut Sorting, _orders: &mut impl Orders<SuperMsg>) {
+pub fn update(
+    msg: &Msg,
+    sorting: &mut Sorting,
+    custom: &mut CustomSortings,
+    _orders: &mut impl Orders<SuperMsg>,
+) {
     match msg {
         Msg::ApplyPreset(preset) => preset.apply_to(sorting),
+        Msg::ApplyCustom(idx) => {
+            if let Some(c) = custom.0.get(*idx) {
+                *sorting = c.sorting.clone();
+            }
+        }
+        Msg::SaveCustom(name) => {
+            let saved = CustomSorting {
+                name: name.clone(),
+                sorting: sorting.clone(),
+            };
+            if let Some(c) = custom.0.iter_mut().find(|c| &c.name == name) {
+                *c = saved;
+            } else {
+                custom.0.push(saved);
+            }
+            custom.updated();
+        }
+        Msg::DeleteCustom(idx) => {
+            if *idx < custom.0.len() {
+                custom.0.remove(*idx);
+                custom.updated();
+            }
+        }
+        Msg::SetMode(mode) => sorting.mode = *mode,
+        Msg::AddFragment(fragment) => sorting.fragments.push(WeightedFragment {
+            fragment: *fragment,
+            weight: 1.0,
+        }),
+        Msg::RemoveFragment(idx) => {
+            if *idx < sorting.fragments.len() {
+                sorting.fragments.remove(*idx);
+            }
+        }
+        Msg::MoveFragmentUp(idx) => {
+            if (1..sorting.fragments.len()).contains(idx) {
+                sorting.fragments.swap(idx - 1, *idx);
+            }
+        }
+        Msg::SetWeight(idx, weight) => {
+            if let Some(f) = sorting
+                .fragments
+                .get_mut(*idx)
+                .filter(|_| weight.is_finite())
+            {
+                f.weight = *weight;
+            }
+        }
     }
     sorting.updated();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(strength: i16, prayer: i16) -> CombatStats {
+        CombatStats {
+            melee_strength: strength,
+            prayer,
+            ..CombatStats::default()
+        }
+    }
+
+    #[test]
+    fn gp_per_stat_point() {
+        // Cheap, expensive, untradeable and neutral items
+        let items = [
+            (stats(4, 0), Some(1_000)),
+            (stats(8, 0), Some(80_000)),
+            (stats(6, 0), None),
+            (CombatStats::default(), Some(0)),
+        ];
+        let ranking = |sorting: &Sorting| {
+            let keys: Vec<_> = items
+                .iter()
+                .map(|(stats, price)| sorting.key(stats, *price))
+                .collect();
+            assert!(keys.iter().flatten().all(|v| !v.is_nan()));
+            let mut order: Vec<_> = (0..keys.len()).collect();
+            order.sort_by(|&a, &b| compare_keys(&keys[a], &keys[b]));
+            order
+        };
+
+        // Price alone uses all stats as points, items without a gp per point value tie last
+        let price_only = Sorting::lexicographic(&[Fragment::GpPerStatPoint]);
+        let order = ranking(&price_only);
+        assert_eq!(order[..2], [0, 1]);
+        assert_eq!(
+            price_only.key(&items[2].0, items[2].1),
+            price_only.key(&items[3].0, items[3].1)
+        );
+        assert_eq!(
+            price_only.stat_fragments().len(),
+            Fragment::VARIANT_COUNT - 1
+        );
+
+        // Weighted: -250 + 4 and -10000 + 8 for the priced items, then the unpriced items by
+        // their remaining stats
+        let mut weighted =
+            Sorting::lexicographic(&[Fragment::GpPerStatPoint, Fragment::MeleeStrength]);
+        weighted.mode = Mode::Weighted;
+        assert_eq!(weighted.key(&items[0].0, items[0].1), [1.0, -246.0]);
+        assert_eq!(weighted.key(&items[2].0, items[2].1), [0.0, 6.0]);
+        assert_eq!(ranking(&weighted), [0, 1, 2, 3]);
+
+        // Stat fragments decide which items are kept
+        let neutral = price_only.stat_key(&price_only.stat_fragments(), &CombatStats::default());
+        let kept = price_only.stat_key(&price_only.stat_fragments(), &stats(1, 0));
+        assert_eq!(compare_keys(&kept, &neutral), Ordering::Less);
+    }
+
+    #[test]
+    fn stored_formats() {
+        let old: Sorting = serde_json::from_str(r#"["MeleeStrength", "Prayer"]"#).unwrap();
+        assert_eq!(
+            old,
+            Sorting::lexicographic(&[Fragment::MeleeStrength, Fragment::Prayer])
+        );
+
+        let mut current = old;
+        current.mode = Mode::Weighted;
+        current.fragments[1].weight = 2.5;
+        let json = serde_json::to_string(&current).unwrap();
+        assert_eq!(serde_json::from_str::<Sorting>(&json).unwrap(), current);
+    }
+}
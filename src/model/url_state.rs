@@ -0,0 +1,113 @@
+//! Encodes the view settings in the URL, to allow sharing links to a view and restoring previous
+//! views when navigating back and forward.
+
+use data::EquipSlot;
+use seed::Url;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "trailblazer")]
+use super::region_filter::RegionFilter;
+use super::{filter::Filter, sorting::Sorting, Model};
+
+/// Prefix of the URL fragment holding the state.
+const HASH_PREFIX: &str = "s=";
+
+/// The settings stored in the URL.
+///
+/// Missing fields fall back to their defaults, so links stay valid when settings are added. The
+/// player's levels and unlocks are not part of the view and left out.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UrlState {
+    list: Option<EquipSlot>,
+    sorting: Sorting,
+    filter: Filter,
+    #[cfg(feature = "trailblazer")]
+    trailblazer: RegionFilter,
+    pinned: Vec<u32>,
+}
+
+impl UrlState {
+    /// Collect the settings of `model`.
+    #[must_use]
+    pub fn new(model: &Model) -> Self {
+        Self {
+            list: model.list,
+            sorting: model.sorting.clone(),
+            filter: model.filter.without_player(),
+            #[cfg(feature = "trailblazer")]
+            trailblazer: model.trailblazer.clone(),
+            pinned: model.pinned.clone(),
+        }
+    }
+
+    /// Read the settings from the fragment of `url`. Returns `None` if the URL holds no valid
+    /// settings.
+    #[must_use]
+    pub fn from_url(url: &Url) -> Option<Self> {
+        let encoded = url.hash()?.strip_prefix(HASH_PREFIX)?;
+        let json = base64::decode_config(encoded, base64::URL_SAFE_NO_PAD).ok()?;
+        serde_json::from_slice(&json).ok()
+    }
+
+    /// Encode the settings as URL fragment.
+    #[must_use]
+    pub fn to_hash(&self) -> String {
+        let json = serde_json::to_vec(self).unwrap_or_default();
+        format!(
+            "{}{}",
+            HASH_PREFIX,
+            base64::encode_config(json, base64::URL_SAFE_NO_PAD)
+        )
+    }
+
+    /// Replace the settings of `model`, keeping the player's levels and unlocks. Returns `true`
+    /// if the sorting changed.
+    ///
+    /// Nothing is written to web storage, the settings are only stored once the user changes them.
+    pub fn apply_to(self, model: &mut Model) -> bool {
+        let resort = model.sorting != self.sorting;
+
+        model.list = self.list;
+        model.sorting = self.sorting;
+        model.filter = self.filter.with_player_of(&model.filter);
+        #[cfg(feature = "trailblazer")]
+        {
+            model.trailblazer = self.trailblazer;
+        }
+        model.pinned = self.pinned;
+
+        resort
+    }
+}
+
+/// Add the settings of `model` to the browser history, if they differ from the current URL.
+///
+/// A URL without settings is replaced instead, so going back does not lead to a view without
+/// settings.
+pub fn push(model: &Model) {
+    set(model, false);
+}
+
+/// Replace the current browser history entry with the settings of `model`.
+///
+/// Used for changes made on every keystroke, e.g. searching, which would flood the history.
+pub fn replace(model: &Model) {
+    set(model, true);
+}
+
+fn set(model: &Model, replace: bool) {
+    let hash = UrlState::new(model).to_hash();
+    let url = Url::current();
+    if url.hash() == Some(&hash) {
+        return;
+    }
+
+    let replace = replace || UrlState::from_url(&url).is_none();
+    let url = url.set_hash(hash);
+    if replace {
+        url.go_and_replace();
+    } else {
+        url.go_and_push();
+    }
+}
@@ -0,0 +1,36 @@
+//! Displays buttons to describe the situation of the fight for conditional item bonuses.
+
+use data::{Context, MonsterAttribute};
+use seed::prelude::*;
+use seed::{div, span, C, IF};
+
+use crate::model::{context::ContextMsg, Msg};
+
+/// Attributes for which items with conditional bonuses exist.
+const ATTRIBUTES: &[MonsterAttribute] = &[
+    MonsterAttribute::Undead,
+    MonsterAttribute::Demon,
+    MonsterAttribute::Dragon,
+];
+
+/// Create the DOM according to the [`Context`].
+pub fn view(context: &Context) -> Node<Msg> {
+    div![
+        view_button("Slayer task", context.slayer_task, ContextMsg::SlayerTask),
+        view_button("Wilderness", context.wilderness, ContextMsg::Wilderness),
+        ATTRIBUTES.iter().map(|&a| view_button(
+            &format!("{:?}", a),
+            context.attributes.contains(&a),
+            ContextMsg::Attribute(a)
+        )),
+    ]
+}
+
+fn view_button(text: &str, checked: bool, msg: ContextMsg) -> Node<Msg> {
+    span![
+        C!["button"],
+        IF!(checked => C!["checked"]),
+        ev(Ev::Click, move |_| Msg::Context(msg)),
+        text
+    ]
+}
@@ -1,38 +1,56 @@
 //! View the currently selected equipment set with a similar layout to the in-game equipment panel.
 
-use data::EquipSlot;
+use data::{EquipSlot, Item, Loadout};
 use enum_iterator::IntoEnumIterator;
 use seed::prelude::*;
-use seed::{attrs, div, img, style, C};
+use seed::{attrs, div, img, style, C, IF};
 
 use crate::model::{Model, Msg};
 
-/// Create the DOM according to the [`Model`].
-pub fn view(model: &Model) -> Node<Msg> {
+/// Create the DOM according to the [`Loadout`].
+pub fn view(model: &Model, loadout: &Loadout) -> Node<Msg> {
     div![
         C!["equipment-view"],
-        EquipSlot::into_enum_iter().map(|s| view_slot(model, s))
+        EquipSlot::into_enum_iter().map(|s| view_slot(model, loadout, s))
     ]
 }
 
-fn view_slot(model: &Model, slot: EquipSlot) -> Node<Msg> {
+fn view_slot(model: &Model, loadout: &Loadout, slot: EquipSlot) -> Node<Msg> {
     let (left, top) = get_offsets(slot);
 
     let left = format!("{}px", left);
     let top = format!("{}px", top);
 
-    if let Some(item) = model.get_item(slot, 0) {
-        let icon = format!("data:image/bmp;base64,{}", base64::encode(&item.icon_data));
-
-        div![
-            ev(Ev::Click, move |_| Msg::ChangeList(slot)),
-            C!["equipment equipment-blank"],
-            style!["left" => left, "top" => top],
-            img![attrs![At::Src => icon, At::Title => item.name]],
-        ]
-    } else {
-        Node::Empty
-    }
+    // Two handed items are shown in the weapon slot, the extra tile shows the unused alternative
+    let (item, unused) = match slot {
+        EquipSlot::Weapon => (
+            loadout[EquipSlot::Weapon].or(loadout[EquipSlot::TwoHanded]),
+            false,
+        ),
+        EquipSlot::TwoHanded => {
+            let alternative = if loadout[EquipSlot::TwoHanded].is_some() {
+                EquipSlot::Weapon
+            } else {
+                EquipSlot::TwoHanded
+            };
+            (model.get_item(alternative, 0), true)
+        }
+        _ => (loadout[slot], false),
+    };
+    let list = item.map_or(slot, |i| i.equip_slot);
+
+    div![
+        ev(Ev::Click, move |_| Msg::ChangeList(list)),
+        C!["equipment equipment-blank"],
+        IF!(unused => C!["equipment-unused"]),
+        style!["left" => left, "top" => top],
+        item.map(view_icon),
+    ]
+}
+
+fn view_icon(item: &Item) -> Node<Msg> {
+    let icon = format!("data:image/bmp;base64,{}", base64::encode(&item.icon_data));
+    img![attrs![At::Src => icon, At::Title => item.name]]
 }
 
 fn get_offsets(slot: EquipSlot) -> (u8, u8) {
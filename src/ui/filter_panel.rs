@@ -0,0 +1,124 @@
+//! Displays inputs to change the general item filters.
+
+use data::{Clue, PlayerLevels, Skill};
+use seed::prelude::*;
+use seed::{attrs, div, input, label, option, select, span, C, IF};
+
+use crate::model::{
+    filter::{Filter, FilterMsg},
+    Msg,
+};
+
+/// Skills whose level requirements can be limited, e.g. for accounts with low Defence.
+const LIMITED_SKILLS: [Skill; 6] = [
+    Skill::Attack,
+    Skill::Strength,
+    Skill::Defence,
+    Skill::Ranged,
+    Skill::Magic,
+    Skill::Prayer,
+];
+
+/// Create the DOM according to the [`Filter`].
+pub fn view(filter: &Filter) -> Node<Msg> {
+    div![
+        view_button("F2P only", filter.f2p(), || FilterMsg::ToggleF2p),
+        view_button("Tradeable only", filter.tradeable(), || {
+            FilterMsg::ToggleTradeable
+        }),
+        view_button("No mega-rares", filter.mega_rares(), || {
+            FilterMsg::ToggleMegaRares
+        }),
+        view_button("Ironman", filter.ironman(), || FilterMsg::ToggleIronman),
+        label![
+            "Clues up to ",
+            select![
+                option![
+                    attrs![At::Value => "", At::Selected => filter.max_clue().is_none().as_at_value()],
+                    "Master"
+                ],
+                Clue::TIERS[..Clue::TIERS.len() - 1]
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, clue)| option![
+                        attrs![
+                            At::Value => idx,
+                            At::Selected => (filter.max_clue() == Some(*clue)).as_at_value(),
+                        ],
+                        clue.name()
+                    ]),
+                input_ev(Ev::Change, |value| Msg::Filter(FilterMsg::SetMaxClue(
+                    value
+                        .parse()
+                        .ok()
+                        .and_then(|idx: usize| Clue::TIERS.get(idx).copied())
+                ))),
+            ],
+        ],
+        label![
+            "Max price ",
+            input![
+                attrs![
+                    At::Type => "number",
+                    At::Min => 0,
+                    At::Value => filter.max_price().map_or_else(String::new, |p| p.to_string()),
+                ],
+                input_ev(Ev::Change, |value| Msg::Filter(FilterMsg::SetMaxPrice(
+                    value.parse().ok()
+                ))),
+            ],
+        ],
+        label![
+            "Loadout budget ",
+            input![
+                attrs![
+                    At::Type => "number",
+                    At::Min => 0,
+                    At::Value => filter.budget().map_or_else(String::new, |b| b.to_string()),
+                ],
+                input_ev(Ev::Change, |value| Msg::Filter(FilterMsg::SetBudget(
+                    value.parse().ok()
+                ))),
+            ],
+        ],
+        span![
+            "Max requirements ",
+            LIMITED_SKILLS
+                .iter()
+                .map(|&skill| view_limit(filter, skill)),
+        ],
+        label![
+            "Search ",
+            input![
+                attrs![At::Type => "search", At::Value => filter.search()],
+                input_ev(Ev::Input, |value| Msg::Filter(FilterMsg::SetSearch(value))),
+            ],
+        ],
+    ]
+}
+
+fn view_limit(filter: &Filter, skill: Skill) -> Node<Msg> {
+    label![
+        format!("{:?} ", skill),
+        input![
+            attrs![
+                At::Type => "number",
+                At::Min => 1,
+                At::Max => PlayerLevels::MAX_LEVEL,
+                At::Value => filter.requirement_limit(skill).map_or_else(String::new, |l| l.to_string()),
+            ],
+            input_ev(Ev::Change, move |value| Msg::Filter(
+                FilterMsg::SetRequirementLimit(skill, value.parse().ok())
+            )),
+        ],
+    ]
+}
+
+fn view_button(text: &str, checked: bool, msg: fn() -> FilterMsg) -> Node<Msg> {
+    span![
+        C!["button"],
+        IF!(checked => C!["checked"]),
+        ev(Ev::Click, move |_| Msg::Filter(msg())),
+        text
+    ]
+}
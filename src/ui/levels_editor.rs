@@ -0,0 +1,106 @@
+//! Displays inputs to edit the player's levels and unlocks used for requirement filtering.
+
+use data::{PlayerLevels, Requirement, Skill, Unlocks};
+use enum_iterator::IntoEnumIterator;
+use seed::prelude::*;
+use seed::{attrs, div, input, label, option, select, span, C, IF};
+
+use crate::model::{filter::FilterMsg, Model, Msg};
+
+/// Create the DOM according to the [`Filter`](crate::model::filter::Filter) of the [`Model`].
+pub fn view(model: &Model) -> Node<Msg> {
+    let filter = &model.filter;
+    let levels = filter.levels();
+
+    div![
+        span![
+            C!["button"],
+            IF!(filter.usable() => C!["checked"]),
+            ev(Ev::Click, |_| Msg::Filter(FilterMsg::ToggleUsable)),
+            "Usable only"
+        ],
+        IF!(filter.usable() => div![
+            C!["levels-editor"],
+            Skill::into_enum_iter().map(|s| view_skill(levels, s)),
+            span![format!("Combat level: {}", levels.combat_level())],
+        ]),
+        IF!(filter.usable() => view_unlocks(model, filter.unlocks())),
+    ]
+}
+
+fn view_skill(levels: &PlayerLevels, skill: Skill) -> Node<Msg> {
+    label![
+        format!("{:?}", skill),
+        input![
+            attrs![
+                At::Type => "number",
+                At::Min => PlayerLevels::min_level(skill),
+                At::Max => PlayerLevels::MAX_LEVEL,
+                At::Value => levels.get(skill),
+            ],
+            input_ev(Ev::Change, move |value| value
+                .parse()
+                .ok()
+                .map(|level| Msg::Filter(FilterMsg::SetLevel(skill, level)))),
+        ],
+    ]
+}
+
+fn view_unlocks(model: &Model, unlocks: &Unlocks) -> Node<Msg> {
+    let (quests, diaries) = model.unlock_names();
+
+    div![
+        C!["levels-editor"],
+        quests.into_iter().map(|quest| {
+            let name = quest.to_string();
+            span![
+                C!["small-button"],
+                IF!(unlocks.quest(quest) => C!["checked"]),
+                ev(Ev::Click, move |_| Msg::Filter(FilterMsg::ToggleQuest(
+                    name
+                ))),
+                quest
+            ]
+        }),
+        diaries.into_iter().map(|region| {
+            let name = region.to_string();
+            view_tier(
+                &format!("{} Diary", region),
+                &Requirement::DIARY_TIERS,
+                unlocks.diary(region),
+                move |tier| FilterMsg::SetDiary(name.clone(), tier),
+            )
+        }),
+        view_tier(
+            "Combat Achievements",
+            &Requirement::COMBAT_ACHIEVEMENT_TIERS,
+            unlocks.combat_achievements(),
+            FilterMsg::SetCombatAchievements,
+        ),
+    ]
+}
+
+fn view_tier(
+    text: &str,
+    tiers: &[&str],
+    current: u8,
+    msg: impl FnOnce(u8) -> FilterMsg + Clone + 'static,
+) -> Node<Msg> {
+    label![
+        text,
+        select![
+            option![
+                attrs![At::Value => 0, At::Selected => (current == 0).as_at_value()],
+                "None"
+            ],
+            (1..).zip(tiers).map(|(tier, name)| option![
+                attrs![At::Value => tier, At::Selected => (current == tier).as_at_value()],
+                name
+            ]),
+            input_ev(Ev::Change, move |value| value
+                .parse()
+                .ok()
+                .map(|tier| Msg::Filter(msg(tier)))),
+        ],
+    ]
+}
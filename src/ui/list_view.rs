@@ -3,20 +3,23 @@
 use data::{DamageType, Item};
 use enum_iterator::IntoEnumIterator;
 use seed::prelude::*;
-use seed::{a, attrs, img, table, td, tr};
+use seed::{a, attrs, img, span, table, td, tr, C, IF};
 
 use crate::model::{Model, Msg};
 
 /// Create the DOM according to the [`Model`].
 pub fn view(model: &Model) -> Node<Msg> {
     if let Some(slot) = model.list {
-        table![model.iter(slot).map(view_item)]
+        table![model
+            .iter(slot)
+            .map(|item| view_item(item, model.is_pinned(item.id)))]
     } else {
         Node::Empty
     }
 }
 
-fn view_item(item: &Item) -> Node<Msg> {
+fn view_item(item: &Item, pinned: bool) -> Node<Msg> {
+    let id = item.id;
     let icon = format!("data:image/bmp;base64,{}", base64::encode(&item.icon_data));
 
     tr![
@@ -36,5 +39,23 @@ fn view_item(item: &Item) -> Node<Msg> {
         td![item.combat_stats.ranged_strength.to_string()],
         td![format!("{}%", item.combat_stats.magic_damage)],
         td![item.combat_stats.prayer.to_string()],
+        td![item
+            .price
+            .market
+            .map_or_else(String::new, |p| format!("{p} gp"))],
+        td![item
+            .attainability
+            .sources
+            .iter()
+            .flatten()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("; ")],
+        td![span![
+            C!["pin"],
+            IF!(pinned => C!["checked"]),
+            ev(Ev::Click, move |_| Msg::TogglePin(id)),
+            if pinned { "Unpin" } else { "Pin" }
+        ]],
     ]
 }
@@ -1,13 +1,19 @@
 //! User interface code.
 
+mod context_buttons;
 mod equipment_view;
+mod filter_panel;
+mod levels_editor;
 mod list_view;
+mod optimizer_view;
 #[cfg(feature = "trailblazer")]
 mod region_buttons;
+mod risk_view;
+mod sorting_builder;
 mod sorting_preset_buttons;
 mod stats_view;
 
-use seed::{div, prelude::*};
+use seed::{div, h3, p, prelude::*, C};
 
 use crate::model::{Model, Msg};
 
@@ -15,13 +21,22 @@ use crate::model::{Model, Msg};
 #[cfg(not(feature = "trailblazer"))]
 #[must_use]
 pub fn view(model: &Model) -> Node<Msg> {
-    if model.is_loading() {
+    if let Some(error) = model.load_error() {
+        view_error(error)
+    } else if model.is_loading() {
         div!["Loading..."]
     } else {
+        let loadout = model.loadout();
         div![
-            sorting_preset_buttons::view(),
-            equipment_view::view(model),
-            stats_view::view(model),
+            sorting_preset_buttons::view(&model.custom_sortings),
+            sorting_builder::view(model.sorting()),
+            context_buttons::view(&model.context),
+            filter_panel::view(&model.filter),
+            levels_editor::view(model),
+            equipment_view::view(model, &loadout),
+            stats_view::view(&loadout),
+            risk_view::view(&model.risk, &loadout),
+            optimizer_view::view(model),
             list_view::view(model),
         ]
     }
@@ -30,15 +45,33 @@ pub fn view(model: &Model) -> Node<Msg> {
 /// Create the DOM according to the [`Model`].
 #[cfg(feature = "trailblazer")]
 pub fn view(model: &Model) -> Node<Msg> {
-    if model.is_loading() {
+    if let Some(error) = model.load_error() {
+        view_error(error)
+    } else if model.is_loading() {
         div!["Loading..."]
     } else {
+        let loadout = model.loadout();
         div![
             region_buttons::view(&model.trailblazer),
-            sorting_preset_buttons::view(),
-            equipment_view::view(model),
-            stats_view::view(model),
+            sorting_preset_buttons::view(&model.custom_sortings),
+            sorting_builder::view(model.sorting()),
+            context_buttons::view(&model.context),
+            filter_panel::view(&model.filter),
+            levels_editor::view(model),
+            equipment_view::view(model, &loadout),
+            stats_view::view(&loadout),
+            risk_view::view(&model.risk, &loadout),
+            optimizer_view::view(model),
             list_view::view(model),
         ]
     }
 }
+
+fn view_error(error: &str) -> Node<Msg> {
+    div![
+        C!["error"],
+        h3!["Item data could not be loaded"],
+        p![error],
+        p!["Reloading the page might help. If the problem persists, please open an issue."],
+    ]
+}
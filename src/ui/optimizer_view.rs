@@ -0,0 +1,118 @@
+//! Displays the settings and results of the search for the loadouts with the highest damage per
+//! second against a monster.
+
+use data::{MonsterDatabase, Prayer};
+use enum_iterator::IntoEnumIterator;
+use seed::prelude::*;
+use seed::{a, attrs, datalist, div, h3, input, label, option, p, span, table, td, th, tr, C, IF};
+
+use crate::model::{
+    optimizer::{OptimizerMsg, OptimizerResult, OptimizerSettings},
+    Model, Msg,
+};
+
+const MONSTER_LIST: &str = "monster-names";
+
+/// Create the DOM according to the [`OptimizerSettings`] of the [`Model`].
+pub fn view(model: &Model) -> Node<Msg> {
+    let settings = &model.optimizer;
+
+    let monsters = match (model.monsters(), model.monster_error()) {
+        (Some(monsters), _) => monsters,
+        (None, Some(error)) => {
+            return div![
+                h3!["Best DPS"],
+                p!["Monster data could not be loaded: ", error]
+            ]
+        }
+        (None, None) => return div![h3!["Best DPS"], p!["Loading monsters..."]],
+    };
+    let monster = monsters.find_by_name(&settings.monster);
+
+    div![
+        h3!["Best DPS"],
+        view_settings(settings, monsters),
+        IF!(monster.is_some() => span![
+            C!["button"],
+            ev(Ev::Click, |_| Msg::Optimize),
+            "Find best loadouts"
+        ]),
+        IF!(!settings.results.is_empty() => table![
+            tr![th!["DPS"], th!["Max hit"], th!["Accuracy"], th!["Style"], th!["Items"]],
+            settings.results.iter().map(|r| view_result(model, r)),
+        ]),
+    ]
+}
+
+fn view_settings(settings: &OptimizerSettings, monsters: &MonsterDatabase) -> Node<Msg> {
+    div![
+        label![
+            "Monster",
+            input![
+                attrs![At::List => MONSTER_LIST, At::Value => settings.monster],
+                input_ev(Ev::Change, |name| Msg::Optimizer(OptimizerMsg::SetMonster(
+                    name
+                ))),
+            ],
+            datalist![
+                attrs![At::Id => MONSTER_LIST],
+                monsters
+                    .iter()
+                    .map(|m| option![attrs![At::Value => m.name]]),
+            ],
+        ],
+        label![
+            "Spell max hit",
+            input![
+                attrs![At::Type => "number", At::Min => 0, At::Value => settings.spell_max_hit],
+                input_ev(Ev::Change, |value| value.parse().ok().map(|max_hit| {
+                    Msg::Optimizer(OptimizerMsg::SetSpellMaxHit(max_hit))
+                })),
+            ],
+        ],
+        label![
+            "Current hitpoints",
+            input![
+                attrs![
+                    At::Type => "number",
+                    At::Min => 1,
+                    At::Placeholder => "Full",
+                    At::Value => settings.current_hitpoints.map_or_else(String::new, |hp| hp.to_string()),
+                ],
+                input_ev(Ev::Change, |value| Msg::Optimizer(
+                    OptimizerMsg::SetCurrentHitpoints(value.parse().ok())
+                )),
+            ],
+        ],
+        Prayer::into_enum_iter().map(|prayer| span![
+            C!["button"],
+            IF!(settings.prayers.contains(&prayer) => C!["checked"]),
+            ev(Ev::Click, move |_| Msg::Optimizer(
+                OptimizerMsg::TogglePrayer(prayer)
+            )),
+            prayer.name()
+        ]),
+    ]
+}
+
+fn view_result(model: &Model, result: &OptimizerResult) -> Node<Msg> {
+    let damage = &result.damage;
+
+    tr![
+        td![format!("{:.2}", damage.dps())],
+        td![damage.max_hit.to_string()],
+        td![format!("{:.1}%", damage.hit_chance * 100.0)],
+        td![&result.option],
+        td![result
+            .items
+            .iter()
+            .filter_map(|&id| model.get_by_id(id))
+            .map(|item| span![
+                a![
+                    attrs![At::Href => item.wiki_url, At::Title => item.name],
+                    &item.name
+                ],
+                " "
+            ])],
+    ]
+}
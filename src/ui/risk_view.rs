@@ -0,0 +1,54 @@
+//! Displays the items kept and lost on death in the wilderness with the current loadout.
+
+use data::{Item, Loadout, Risk};
+use seed::prelude::*;
+use seed::{a, attrs, div, h3, span, table, td, tr, C, IF};
+
+use crate::model::{
+    risk::{RiskMsg, RiskSettings},
+    Msg,
+};
+
+/// Create the DOM according to the [`RiskSettings`] and the [`Loadout`].
+pub fn view(settings: &RiskSettings, loadout: &Loadout) -> Node<Msg> {
+    let risk = Risk::new(loadout, settings.skulled, settings.protect_item);
+
+    div![
+        h3!["Wilderness Risk"],
+        view_button("Skulled", settings.skulled, RiskMsg::Skulled),
+        view_button("Protect Item", settings.protect_item, RiskMsg::ProtectItem),
+        table![
+            tr![td!["Kept"], td![view_items(&risk.kept)]],
+            tr![td!["Lost"], td![view_items(&risk.lost)]],
+            tr![td!["GP lost"], td![format!("{} gp", risk.gp_lost)]],
+            tr![
+                td!["Reclaim cost"],
+                td![format!("{} gp", risk.reclaim_cost)]
+            ],
+        ],
+    ]
+}
+
+fn view_button(text: &str, checked: bool, msg: RiskMsg) -> Node<Msg> {
+    span![
+        C!["button"],
+        IF!(checked => C!["checked"]),
+        ev(Ev::Click, move |_| Msg::Risk(msg)),
+        text
+    ]
+}
+
+fn view_items(items: &[&Item]) -> Vec<Node<Msg>> {
+    items
+        .iter()
+        .map(|item| {
+            span![
+                a![
+                    attrs![At::Href => item.wiki_url, At::Title => item.name],
+                    &item.name
+                ],
+                " "
+            ]
+        })
+        .collect()
+}
@@ -0,0 +1,84 @@
+//! Displays inputs to build a custom sorting from fragments.
+
+use enum_iterator::IntoEnumIterator;
+use seed::prelude::*;
+use seed::{attrs, div, input, label, option, select, span, table, td, tr, C, IF};
+
+use crate::model::{
+    sorting::{Fragment, Mode, Msg as SortingMsg, Sorting, WeightedFragment},
+    Msg,
+};
+
+/// Create the DOM according to the [`Sorting`].
+pub fn view(sorting: &Sorting) -> Node<Msg> {
+    let weighted = sorting.mode() == Mode::Weighted;
+
+    div![
+        span![
+            C!["button"],
+            IF!(weighted => C!["checked"]),
+            ev(Ev::Click, move |_| Msg::Sorting(SortingMsg::SetMode(
+                if weighted {
+                    Mode::Lexicographic
+                } else {
+                    Mode::Weighted
+                }
+            ))),
+            "Weighted"
+        ],
+        table![sorting
+            .fragments()
+            .iter()
+            .enumerate()
+            .map(|(idx, &f)| view_fragment(idx, f, weighted))],
+        label![
+            "Add ",
+            select![
+                option![attrs![At::Value => ""], "-"],
+                Fragment::into_enum_iter()
+                    .enumerate()
+                    .map(|(idx, f)| option![attrs![At::Value => idx], format!("{:?}", f)]),
+                input_ev(Ev::Change, |value| value
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|idx| Fragment::into_enum_iter().nth(idx))
+                    .map(|f| Msg::Sorting(SortingMsg::AddFragment(f)))),
+            ],
+        ],
+        label![
+            "Save as ",
+            input![
+                attrs![At::Placeholder => "Name"],
+                input_ev(Ev::Change, |name| {
+                    let name = name.trim().to_string();
+                    (!name.is_empty()).then_some(Msg::Sorting(SortingMsg::SaveCustom(name)))
+                }),
+            ],
+        ],
+    ]
+}
+
+fn view_fragment(idx: usize, fragment: WeightedFragment, weighted: bool) -> Node<Msg> {
+    tr![
+        td![format!("{:?}", fragment.fragment)],
+        IF!(weighted => td![input![
+            attrs![At::Type => "number", At::Step => "0.1", At::Value => fragment.weight],
+            input_ev(Ev::Change, move |value| value
+                .parse()
+                .ok()
+                .map(|weight| Msg::Sorting(SortingMsg::SetWeight(idx, weight)))),
+        ]]),
+        td![IF!(idx > 0 && !weighted => span![
+            C!["small-button"],
+            ev(Ev::Click, move |_| Msg::Sorting(SortingMsg::MoveFragmentUp(idx))),
+            "Up"
+        ])],
+        td![span![
+            C!["small-button"],
+            ev(Ev::Click, move |_| Msg::Sorting(
+                SortingMsg::RemoveFragment(idx)
+            )),
+            "Remove"
+        ]],
+    ]
+}
@@ -1,17 +1,34 @@
-//! Displays buttons to allow applying sorting presets
+//! Displays buttons to allow applying sorting presets and user-defined sortings
 
 use enum_iterator::IntoEnumIterator;
 use seed::prelude::*;
 use seed::{div, span, C};
 
 use crate::model::{
-    sorting::{Msg as SortingMsg, Preset},
+    sorting::{CustomSortings, Msg as SortingMsg, Preset},
     Msg,
 };
 
-/// Create the DOM according to the [`Model`].
-pub fn view() -> Node<Msg> {
-    div![Preset::into_enum_iter().map(view_button)]
+/// Create the DOM according to the [`Model`](crate::model::Model).
+pub fn view(custom: &CustomSortings) -> Node<Msg> {
+    div![
+        Preset::into_enum_iter().map(view_button),
+        custom.iter().enumerate().map(|(idx, c)| span![
+            C!["button"],
+            ev(Ev::Click, move |_| Msg::Sorting(SortingMsg::ApplyCustom(
+                idx
+            ))),
+            &c.name,
+            span![
+                C!["delete"],
+                ev(Ev::Click, move |event| {
+                    event.stop_propagation();
+                    Msg::Sorting(SortingMsg::DeleteCustom(idx))
+                }),
+                " ×"
+            ],
+        ]),
+    ]
 }
 
 fn view_button(preset: Preset) -> Node<Msg> {
@@ -1,18 +1,17 @@
-//! View the currently selected equipment set with a similar layout to the in-game equipment panel.
+//! View the combined stats of the currently selected equipment set.
 
-use data::{CombatStats, DamageType, EquipSlot};
+use data::{DamageType, Loadout};
 use enum_iterator::IntoEnumIterator;
 use seed::prelude::*;
-use seed::{div, h3, table, td, tr};
+use seed::{div, h3, table, td, tr, IF};
 
-use crate::model::{Model, Msg};
+use crate::model::Msg;
 
-/// Create the DOM according to the [`Model`].
-pub fn view(model: &Model) -> Node<Msg> {
-    let mut stats = CombatStats::default();
-    for slot in EquipSlot::into_enum_iter() {
-        stats += &model.get_item(slot, 0).unwrap().combat_stats;
-    }
+/// Create the DOM according to the [`Loadout`].
+pub fn view(loadout: &Loadout) -> Node<Msg> {
+    let stats = loadout.combat_stats();
+    let set_bonuses = loadout.set_bonuses();
+    let reduction = DamageType::into_enum_iter().any(|t| loadout.damage_reduction(t) > 0.0);
 
     div![
         h3!["Attack Bonuses"],
@@ -34,6 +33,22 @@ pub fn view(model: &Model) -> Node<Msg> {
             ],
             tr![td!["Magic Damage"], td![format!("{}%", stats.magic_damage)]],
             tr![td!["Prayer"], td![stats.prayer.to_string()]],
+            tr![td!["Weight"], td![format!("{:.1} kg", loadout.weight())]],
+            tr![td!["Cost"], td![format!("{} gp", loadout.cost())]],
         ],
+        h3!["Requirements"],
+        table![loadout
+            .requirements()
+            .into_iter()
+            .map(|r| tr![td![r.name()], td![r.level_name()]])],
+        IF!(!set_bonuses.is_empty() => h3!["Set Effects"]),
+        table![set_bonuses
+            .iter()
+            .map(|b| tr![td![format!("{:?}", b.set)], td![b.set.description()]])],
+        IF!(reduction => h3!["Damage Reduction"]),
+        IF!(reduction => table![DamageType::into_enum_iter().map(|damage_type| tr![
+            td![format!("{damage_type}")],
+            td![format!("{:.1}%", loadout.damage_reduction(damage_type) * 100.0)]
+        ])]),
     ]
 }